@@ -0,0 +1,12 @@
+pub type Result<T> = core::result::Result<T, AnswersError>;
+
+#[derive(thiserror::Error, Debug)]
+#[error("Answers Error")]
+pub enum AnswersError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("Error parsing answers: {0}")]
+    Parsing(#[from] toml::de::Error),
+    #[error("Invalid day key `{0}`, expected `dayN`")]
+    InvalidDay(String),
+}
@@ -0,0 +1,158 @@
+pub mod error;
+
+use error::{AnswersError, Result};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::solution::{Part, answer::Answer};
+
+/// Answers directory of this repository, resolved at build time
+const ANSWERS_BASE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers/");
+/// Environment variable overriding [`ANSWERS_BASE_PATH`]
+pub const ANSWERS_DIR_ENV: &str = "AOC_ANSWERS_DIR";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InputAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// Known answers of a year, keyed by day, input name and part
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, String, Part), Answer>,
+}
+
+impl Answers {
+    /// Loads `{year}.toml` from the answers directory. A missing file means no known answers.
+    pub fn load(year: u16) -> Result<Self> {
+        Self::load_file(&get_answers_path(get_answers_base_path(), year))
+    }
+
+    /// Loads an answers file. A missing file means no known answers.
+    pub fn load_file(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_str(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get(&self, day: u8, input_name: &str, part: Part) -> Option<&Answer> {
+        self.entries.get(&(day, input_name.to_string(), part))
+    }
+
+    /// Names of the inputs holding at least one answer for the day
+    pub fn input_names(&self, day: u8) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .entries
+            .keys()
+            .filter(|(d, _, _)| *d == day)
+            .map(|(_, name, _)| name.as_str())
+            .collect();
+        names.dedup();
+        names
+    }
+}
+
+impl FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let days: BTreeMap<String, BTreeMap<String, InputAnswers>> = toml::from_str(s)?;
+
+        let mut entries = BTreeMap::new();
+        for (day_key, inputs) in days {
+            let day: u8 = day_key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| AnswersError::InvalidDay(day_key.clone()))?;
+
+            for (input_name, answers) in inputs {
+                for (part, answer) in [(Part::One, answers.part1), (Part::Two, answers.part2)] {
+                    if let Some(answer) = answer {
+                        entries.insert((day, input_name.clone(), part), answer);
+                    }
+                }
+            }
+        }
+        Ok(Self { entries })
+    }
+}
+
+/// Base directory of the answer files, taken from [`ANSWERS_DIR_ENV`] when it is set
+pub fn get_answers_base_path() -> PathBuf {
+    match env::var_os(ANSWERS_DIR_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => fs::canonicalize(ANSWERS_BASE_PATH).unwrap_or_else(|_| ANSWERS_BASE_PATH.into()),
+    }
+}
+
+pub fn get_answers_path(base_path: impl AsRef<Path>, year: u16) -> PathBuf {
+    base_path.as_ref().join(format!("{}.toml", year))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+        [day3.default]
+        part1 = 2592
+        part2 = "2360"
+
+        [day3.multiline]
+        part1 = ["2", "4"]
+
+        [day8.jkpr]
+        part2 = 2085
+    "#;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::from_str(ANSWERS).unwrap();
+
+        let validations = vec![
+            ((3, "default", Part::One), Some(Answer::Integer(2592))),
+            ((3, "default", Part::Two), Some(Answer::Text("2360".into()))),
+            (
+                (3, "multiline", Part::One),
+                Some(Answer::Lines(vec!["2".into(), "4".into()])),
+            ),
+            ((3, "multiline", Part::Two), None),
+            ((8, "jkpr", Part::Two), Some(Answer::Integer(2085))),
+            ((8, "default", Part::One), None),
+        ];
+
+        for ((day, input_name, part), expected_result) in validations.into_iter() {
+            assert_eq!(answers.get(day, input_name, part), expected_result.as_ref());
+        }
+        assert_eq!(answers.input_names(3), vec!["default", "multiline"]);
+        assert_eq!(answers.input_names(8), vec!["jkpr"]);
+        assert!(answers.input_names(1).is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalid_answers() {
+        let validations = ["[three.default]\npart1 = 1", "[day3.default]\npart3 = 1"];
+
+        for input in validations.into_iter() {
+            assert!(Answers::from_str(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_load_answers_file() {
+        let answers = Answers::load(2015).unwrap();
+        assert_eq!(
+            answers.get(8, "default", Part::One),
+            Some(&Answer::Integer(1342))
+        );
+    }
+}
@@ -0,0 +1,474 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error,
+    input::InputSource,
+    output::{Report, Reporter, write_solution},
+    problem::Problem,
+    runner,
+    solution::{Part, answer::Answer},
+};
+
+/// Summary of a set of timing samples, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[u64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median_ns = match n % 2 {
+            0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+            _ => sorted[n / 2],
+        };
+        // Nearest-rank percentile
+        let p95_ns = sorted[(n * 95).div_ceil(100) - 1];
+
+        let mean = sorted.iter().map(|&s| s as f64).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|&s| (s as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Some(Self {
+            min_ns: sorted[0],
+            median_ns,
+            mean_ns: mean.round() as u64,
+            p95_ns,
+            stddev_ns: variance.sqrt().round() as u64,
+        })
+    }
+
+    fn write_text(&self, writer: &mut impl Write, phase: &str) -> io::Result<()> {
+        let d = Duration::from_nanos;
+        writeln!(
+            writer,
+            "  {phase}: min {:?} | median {:?} | mean {:?} | p95 {:?} | stddev {:?}",
+            d(self.min_ns),
+            d(self.median_ns),
+            d(self.mean_ns),
+            d(self.p95_ns),
+            d(self.stddev_ns),
+        )
+    }
+}
+
+/// Timings of a part solved repeatedly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub warmup: usize,
+    pub runs: usize,
+    /// Parsing timings, shared by every part of the problem
+    pub parse: Option<Stats>,
+    pub solve: Option<Stats>,
+}
+
+impl BenchRecord {
+    fn new(problem: &Problem, part: Part, input_name: &str, runs: usize, warmup: usize) -> Self {
+        Self {
+            year: problem.get_year(),
+            day: problem.get_day(),
+            part,
+            input: input_name.to_string(),
+            answer: None,
+            error: None,
+            warmup,
+            runs,
+            parse: None,
+            solve: None,
+        }
+    }
+
+    /// Record of a part that could not be benchmarked at all, e.g. because its input is missing
+    pub fn failed(problem: &Problem, part: Part, input_name: &str, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(problem, part, input_name, 0, 0)
+        }
+    }
+}
+
+/// Runs `warmup` untimed and then `runs` timed iterations of `f`, keeping the last result.
+/// Stops at the first error.
+fn sample<T, E>(
+    runs: usize,
+    warmup: usize,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> (Option<Result<T, E>>, Vec<u64>) {
+    let mut samples = Vec::with_capacity(runs);
+    let mut last = None;
+    for run in 0..(warmup + runs) {
+        let current = Instant::now();
+        let result = f();
+        let elapsed_ns = current.elapsed().as_nanos() as u64;
+
+        let failed = result.is_err();
+        last = Some(result);
+        if failed {
+            break;
+        }
+        if run >= warmup {
+            samples.push(elapsed_ns);
+        }
+    }
+    (last, samples)
+}
+
+/// Benchmarks parsing the input and then solving every part in `parts` from the parsed input
+pub fn bench_problem(
+    problem: &Problem,
+    parts: &[Part],
+    input: &str,
+    input_name: &str,
+    runs: usize,
+    warmup: usize,
+) -> Vec<BenchRecord> {
+    let solution = problem.get_solution();
+
+    let (parsed, parse_samples) = sample(runs, warmup, || solution.parse(input));
+    let parse = Stats::from_samples(&parse_samples);
+
+    parts
+        .iter()
+        .map(|&part| {
+            let mut record = BenchRecord {
+                parse,
+                ..BenchRecord::new(problem, part, input_name, runs, warmup)
+            };
+            let parsed = match &parsed {
+                Some(Ok(parsed)) => parsed,
+                Some(Err(e)) => {
+                    record.error = Some(e.to_string());
+                    return record;
+                }
+                None => return record,
+            };
+
+            let (answer, solve_samples) =
+                sample(runs, warmup, || solution.solve_parsed(part, parsed));
+            match answer {
+                Some(Ok(answer)) => record.answer = Some(answer),
+                Some(Err(e)) => record.error = Some(e.to_string()),
+                None => (),
+            }
+            record.solve = Stats::from_samples(&solve_samples);
+            record
+        })
+        .collect()
+}
+
+/// Benchmarks `parts` of every problem for each of its `input_sources`, reporting the records
+/// with `reporter`
+pub fn bench_problems<W: Write>(
+    problems: Vec<error::Result<Problem>>,
+    parts: &[Part],
+    reporter: Reporter<W, BenchRecord>,
+    input_sources: impl Fn(&Problem) -> error::Result<Vec<InputSource>> + Sync,
+    runs: usize,
+    warmup: usize,
+) -> io::Result<Vec<BenchRecord>> {
+    // Benchmarks run one at a time so that they do not slow each other down
+    runner::solve_problems(
+        problems,
+        parts,
+        1,
+        reporter,
+        input_sources,
+        |problem, parts, input, input_source| {
+            let input_name = input_source.name(problem.get_year(), problem.get_day());
+            bench_problem(problem, parts, input, &input_name, runs, warmup)
+        },
+        BenchRecord::failed,
+    )
+}
+
+impl Report for BenchRecord {
+    fn csv_header() -> &'static [&'static str] {
+        &[
+            "year",
+            "day",
+            "part",
+            "answer",
+            "error",
+            "input",
+            "warmup",
+            "runs",
+            "parse_min_ns",
+            "parse_median_ns",
+            "parse_mean_ns",
+            "parse_p95_ns",
+            "parse_stddev_ns",
+            "solve_min_ns",
+            "solve_median_ns",
+            "solve_mean_ns",
+            "solve_p95_ns",
+            "solve_stddev_ns",
+        ]
+    }
+
+    fn csv_fields(&self) -> Vec<String> {
+        let mut fields = vec![
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.answer
+                .as_ref()
+                .map(Answer::to_string)
+                .unwrap_or_default(),
+            self.error.clone().unwrap_or_default(),
+            self.input.clone(),
+            self.warmup.to_string(),
+            self.runs.to_string(),
+        ];
+        for stats in [self.parse, self.solve] {
+            match stats {
+                Some(s) => fields.extend(
+                    [s.min_ns, s.median_ns, s.mean_ns, s.p95_ns, s.stddev_ns]
+                        .map(|v| v.to_string()),
+                ),
+                None => fields.extend(std::iter::repeat_n(String::new(), 5)),
+            }
+        }
+        fields
+    }
+
+    fn write_text(&self, writer: &mut impl Write) -> io::Result<()> {
+        let title = format!("Part {}:", self.part);
+        if let Some(error) = &self.error {
+            return writeln!(writer, "{} Error: {}\n", title, error);
+        }
+        let answer = self.answer.as_ref().map(Answer::lines).unwrap_or_default();
+        write_solution(writer, &title, &answer)?;
+        writeln!(writer, "  {} runs after {} warmup", self.runs, self.warmup)?;
+        if let Some(parse) = &self.parse {
+            parse.write_text(writer, "parse")?;
+        }
+        if let Some(solve) = &self.solve {
+            solve.write_text(writer, "solve")?;
+        }
+        writeln!(writer)
+    }
+}
+
+pub fn save_records(path: impl AsRef<Path>, records: &[BenchRecord]) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(records)?;
+    fs::write(path, content)
+}
+
+pub fn load_records(path: impl AsRef<Path>) -> io::Result<Vec<BenchRecord>> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Writes the comparison of `records` against the baseline saved in `baseline_path` and tells
+/// whether any part regressed
+pub fn write_baseline_comparison(
+    writer: &mut impl Write,
+    baseline_path: &Path,
+    records: &[BenchRecord],
+    threshold_pct: f64,
+) -> io::Result<bool> {
+    let baseline = load_records(baseline_path)?;
+    writeln!(
+        writer,
+        "---- Comparison against {} ----",
+        baseline_path.display()
+    )?;
+    let mut regression = false;
+    for comparison in compare(&baseline, records, threshold_pct) {
+        comparison.write_text(writer)?;
+        regression |= comparison.regression;
+    }
+    Ok(regression)
+}
+
+/// Change of the median solve time of a part against a baseline run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+    pub change_pct: f64,
+    pub regression: bool,
+}
+
+impl Comparison {
+    pub fn write_text(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "{} Day {} Part {}: {:?} -> {:?} ({:+.1}%){}",
+            self.year,
+            self.day,
+            self.part,
+            Duration::from_nanos(self.baseline_ns),
+            Duration::from_nanos(self.current_ns),
+            self.change_pct,
+            if self.regression { " REGRESSION" } else { "" }
+        )
+    }
+}
+
+/// Compares the median solve times of the records found in both runs. A part regresses when
+/// it is more than `threshold_pct` percent slower than in the baseline.
+pub fn compare(
+    baseline: &[BenchRecord],
+    current: &[BenchRecord],
+    threshold_pct: f64,
+) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|record| {
+            let previous = baseline.iter().find(|b| {
+                (b.year, b.day, b.part, &b.input)
+                    == (record.year, record.day, record.part, &record.input)
+            })?;
+            let (baseline_ns, current_ns) = (previous.solve?.median_ns, record.solve?.median_ns);
+            let change_pct = match baseline_ns {
+                0 => 0.0,
+                _ => (current_ns as f64 - baseline_ns as f64) / baseline_ns as f64 * 100.0,
+            };
+
+            Some(Comparison {
+                year: record.year,
+                day: record.day,
+                part: record.part,
+                input: record.input.clone(),
+                baseline_ns,
+                current_ns,
+                change_pct,
+                regression: change_pct > threshold_pct,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u8, median_ns: u64) -> BenchRecord {
+        BenchRecord {
+            year: 2015,
+            day,
+            part: Part::One,
+            input: format!("day{day}.txt"),
+            answer: Some(Answer::Integer(42)),
+            error: None,
+            warmup: 1,
+            runs: 3,
+            parse: None,
+            solve: Stats::from_samples(&[median_ns, median_ns, median_ns]),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Stats::from_samples(&[]), None);
+
+        let stats = Stats::from_samples(&[5, 1, 3, 2, 4]).unwrap();
+        assert_eq!(stats.min_ns, 1);
+        assert_eq!(stats.median_ns, 3);
+        assert_eq!(stats.mean_ns, 3);
+        assert_eq!(stats.p95_ns, 5);
+        assert_eq!(stats.stddev_ns, 1);
+
+        let stats = Stats::from_samples(&[10, 20, 30, 40]).unwrap();
+        assert_eq!(stats.median_ns, 25);
+        assert_eq!(stats.p95_ns, 40);
+
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.p95_ns, 95);
+        assert_eq!(stats.mean_ns, 51);
+    }
+
+    #[test]
+    fn test_bench_problem() {
+        let problem = Problem::new(2015, 1).unwrap();
+        let records = bench_problem(&problem, &[Part::One], "(()(()(", "<inline>", 5, 2);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].answer, Some(Answer::Integer(3)));
+        assert_eq!(records[0].error, None);
+        assert!(records[0].solve.is_some());
+        assert!(records[0].parse.is_some());
+    }
+
+    #[test]
+    fn test_bench_problem_parse_error() {
+        let problem = Problem::new(2015, 9).unwrap();
+        let records = bench_problem(&problem, &[Part::One, Part::Two], "x", "<inline>", 5, 2);
+
+        assert_eq!(records.len(), 2);
+        for record in records {
+            assert!(record.error.is_some());
+            assert!(record.solve.is_none());
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut calls = 0;
+        let (last, samples) = sample(3, 2, || -> Result<usize, ()> {
+            calls += 1;
+            Ok(calls)
+        });
+        assert_eq!(last, Some(Ok(5)));
+        assert_eq!(samples.len(), 3);
+
+        let (last, samples) = sample(3, 2, || Err::<(), _>("error"));
+        assert_eq!(last, Some(Err("error")));
+        assert!(samples.is_empty());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = vec![record(1, 100), record(2, 100), record(3, 100)];
+        let current = vec![record(1, 105), record(2, 150), record(4, 100)];
+
+        let comparisons = compare(&baseline, &current, 10.0);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].change_pct, 5.0);
+        assert!(!comparisons[0].regression);
+        assert_eq!(comparisons[1].change_pct, 50.0);
+        assert!(comparisons[1].regression);
+    }
+
+    #[test]
+    fn test_save_and_load_records() {
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.json", std::process::id()));
+        let records = vec![record(1, 100), record(2, 200)];
+
+        save_records(&path, &records).unwrap();
+        assert_eq!(load_records(&path).unwrap(), records);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,515 @@
+//! Command line interface of the `AoC` binary
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use clap::{Args, CommandFactory, Parser, Subcommand, value_parser};
+
+use crate::{
+    answers::Answers,
+    bench, client, error,
+    examples::Examples,
+    input::{self, InputSource, error::InputError},
+    output::{self, OutputFormat, Reporter},
+    problem::{
+        Problem,
+        selection::{DaySelection, PartSelection},
+    },
+    runner, scaffold,
+    solution::{Part, answer::Answer, error::SolutionError},
+    submit, verify, watch,
+};
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: Option<RunArgs>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check the answers of the implemented days against the answers file of the year
+    Verify(VerifyArgs),
+    /// Generate the module of a new day and register it, along with an empty input file
+    New(NewArgs),
+    /// Download the inputs of the selected days that are not in the inputs directory yet
+    Fetch(FetchArgs),
+    /// Submit the answer of a part and record the verdict in the submission history
+    Submit(SubmitArgs),
+    /// Rebuild and re-run a day, along with its tests, every time its source or inputs change
+    Watch(WatchArgs),
+    /// Write the input of a day as a Graphviz DOT graph, for the days whose input is a graph
+    Inspect(InspectArgs),
+}
+
+#[derive(Args)]
+struct InspectArgs {
+    year: u16,
+    day: u8,
+    /// Read the input from a file instead of the inputs directory. Use `-` to read from stdin.
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<String>,
+    /// Inspect the named input stored as `{year}/day{day}_{name}.txt` in the inputs directory
+    #[arg(long, value_name = "NAME", conflicts_with = "input")]
+    input_name: Option<String>,
+    /// Directory holding the `{year}/day{day}.txt` inputs [env: AOC_INPUT_DIR]
+    #[arg(long, value_name = "DIR", conflicts_with = "input")]
+    input_dir: Option<PathBuf>,
+    /// Write the graph to FILE instead of stdout, e.g. to render it with `dot -Tsvg FILE`
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    year: u16,
+    day: u8,
+    /// Part of the day to run
+    #[arg(short, long, value_enum, default_value_t)]
+    part: PartSelection,
+    /// Directory holding the `{year}/day{day}.txt` inputs [env: AOC_INPUT_DIR]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+    /// Milliseconds between two checks of the watched files
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    interval: u64,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    year: u16,
+    day: u8,
+    part: Part,
+    /// Submit the given answer instead of solving the part with the default input
+    #[arg(long)]
+    answer: Option<String>,
+    /// Submission history used to refuse known wrong answers, kept in the user data directory by
+    /// default [env: AOC_HISTORY_FILE]
+    #[arg(long, value_name = "FILE")]
+    history: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    year: u16,
+    /// Days to download as a list or range of days, e.g. `1,3,5-9`
+    days: DaySelection,
+    /// Directory holding the `{year}/day{day}.txt` inputs [env: AOC_INPUT_DIR]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    year: u16,
+    day: u8,
+    /// Title of the puzzle. Defaults to `Day {day}`.
+    #[arg(long)]
+    title: Option<String>,
+    /// Directory holding the `{year}/day{day}.txt` inputs [env: AOC_INPUT_DIR]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    year: u16,
+    /// Days to verify as a list or range of days, e.g. `1,3,5-9`. Verifies every day when omitted.
+    days: Option<DaySelection>,
+    /// Part of each day to verify
+    #[arg(short, long, value_enum, default_value_t)]
+    part: PartSelection,
+    /// Directory holding the `{year}/day{day}.txt` inputs [env: AOC_INPUT_DIR]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+}
+
+/// Solves the selected days
+#[derive(Args)]
+struct RunArgs {
+    year: u16,
+    /// Days to solve as a list or range of days, e.g. `1,3,5-9`. Solves every day when omitted.
+    days: Option<DaySelection>,
+    /// Part of each day to solve
+    #[arg(short, long, value_enum, default_value_t)]
+    part: PartSelection,
+    /// Read the input from a file instead of the inputs directory. Use `-` to read from stdin.
+    #[arg(short, long, value_name = "PATH", conflicts_with = "input_str")]
+    input: Option<String>,
+    /// Use the given string as input
+    #[arg(long, value_name = "INPUT")]
+    input_str: Option<String>,
+    /// Directory holding the `{year}/day{day}.txt` inputs [env: AOC_INPUT_DIR]
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["input", "input_str"])]
+    input_dir: Option<PathBuf>,
+    /// Solve the example of the given name, stored as `{year}/examples/day{day}_{name}.txt` in the
+    /// inputs directory, or every example of the day when no name is given
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["input", "input_str"])]
+    example: Option<Option<String>>,
+    /// Solve the named input stored as `{year}/day{day}_{name}.txt` in the inputs directory.
+    /// `default` stands for `{year}/day{day}.txt`.
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["input", "input_str", "example"])]
+    input_name: Option<String>,
+    /// Solve every input of the day found in the inputs directory and compare their answers
+    #[arg(long, conflicts_with_all = ["input", "input_str", "example", "input_name"])]
+    all_inputs: bool,
+    /// Output format of the results
+    #[arg(short, long, value_enum, default_value_t)]
+    format: OutputFormat,
+    /// Give up on parts that take longer than SECS seconds, e.g. `0.5`. Only parts that check for
+    /// cancellation are interrupted, the others are reported as timed out once they finish.
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = parse_timeout,
+        conflicts_with = "bench"
+    )]
+    timeout: Option<Duration>,
+    /// Number of days solved in parallel
+    #[arg(
+        short,
+        long,
+        value_name = "N",
+        default_value_t = 1,
+        conflicts_with = "bench"
+    )]
+    jobs: usize,
+    /// Solve each part N times and report timing statistics
+    #[arg(long, value_name = "N", value_parser = value_parser!(u32).range(1..))]
+    bench: Option<u32>,
+    /// Untimed runs of each part before benchmarking it
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "bench")]
+    warmup: usize,
+    /// Save the benchmark results to a JSON file
+    #[arg(long, value_name = "FILE", requires = "bench")]
+    bench_save: Option<PathBuf>,
+    /// Compare the benchmark results against a file saved with `--bench-save`
+    #[arg(long, value_name = "FILE", requires = "bench")]
+    bench_baseline: Option<PathBuf>,
+    /// Slowdown over the baseline, in percent, that is flagged as a regression
+    #[arg(long, value_name = "PCT", default_value_t = 10.0, requires = "bench")]
+    regression_threshold: f64,
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| {
+            format!(
+                "invalid timeout `{}`, expected a positive number of seconds",
+                s
+            )
+        })
+}
+
+impl RunArgs {
+    fn input_source(&self) -> InputSource {
+        match (&self.input, &self.input_str, &self.input_name) {
+            (Some(path), _, _) => InputSource::from(path.as_str()),
+            (_, Some(content), _) => InputSource::Inline(content.clone()),
+            (_, _, Some(name)) => self.named_input_source(name),
+            (None, None, None) => match &self.input_dir {
+                Some(dir) => InputSource::Dir(dir.clone()),
+                None => InputSource::Default,
+            },
+        }
+    }
+
+    fn named_input_source(&self, name: &str) -> InputSource {
+        named_input_source(name, &self.input_dir)
+    }
+
+    fn input_dir(&self) -> PathBuf {
+        match &self.input_dir {
+            Some(dir) => dir.clone(),
+            None => input::get_input_base_path(),
+        }
+    }
+
+    /// Inputs each problem is solved for
+    fn input_sources(&self, year: u16, day: u8) -> error::Result<Vec<InputSource>> {
+        if let Some(name) = &self.example {
+            let examples = Examples::load(&self.input_dir(), year)?;
+            return Ok(examples
+                .select(day, name.as_deref())?
+                .into_iter()
+                .map(|example| InputSource::File(example.path))
+                .collect());
+        }
+        if self.all_inputs {
+            let input_dir = self.input_dir();
+            return match input::get_input_names(&input_dir, year, day)?.as_slice() {
+                [] => Err(InputError::NoInputs {
+                    path: input_dir.join(year.to_string()),
+                    day,
+                }
+                .into()),
+                names => Ok(names
+                    .iter()
+                    .map(|name| self.named_input_source(name))
+                    .collect()),
+            };
+        }
+        Ok(vec![self.input_source()])
+    }
+}
+
+/// Source of the named input, `default` standing for `{year}/day{day}.txt`
+fn named_input_source(name: &str, input_dir: &Option<PathBuf>) -> InputSource {
+    match (name, input_dir) {
+        (input::DEFAULT_INPUT_NAME, Some(dir)) => InputSource::Dir(dir.clone()),
+        (input::DEFAULT_INPUT_NAME, None) => InputSource::Default,
+        (name, Some(dir)) => InputSource::Named(dir.clone(), name.to_string()),
+        (name, None) => InputSource::Named(input::get_input_base_path(), name.to_string()),
+    }
+}
+
+fn run(args: &RunArgs) -> error::Result<ExitCode> {
+    let problems = runner::select_problems(args.year, args.days.as_ref());
+    let parts = args.part.parts();
+    let input_sources =
+        |problem: &Problem| args.input_sources(problem.get_year(), problem.get_day());
+
+    if let Some(runs) = args.bench {
+        let reporter = Reporter::new(args.format, io::stdout().lock())?;
+        let records = bench::bench_problems(
+            problems,
+            &parts,
+            reporter,
+            input_sources,
+            runs as usize,
+            args.warmup,
+        )?;
+
+        let mut exit_code = ExitCode::SUCCESS;
+        if let Some(baseline_path) = &args.bench_baseline {
+            let mut stderr = io::stderr().lock();
+            let threshold = args.regression_threshold;
+            if bench::write_baseline_comparison(&mut stderr, baseline_path, &records, threshold)? {
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+        if let Some(save_path) = &args.bench_save {
+            bench::save_records(save_path, &records)?;
+        }
+        return Ok(exit_code);
+    }
+
+    let answers = Answers::load(args.year)?;
+    let records = runner::solve_problems(
+        problems,
+        &parts,
+        args.jobs,
+        Reporter::new(args.format, io::stdout().lock())?,
+        input_sources,
+        |problem, parts, input, input_source| {
+            let (year, day) = (problem.get_year(), problem.get_day());
+            let input_name = input_source.name(year, day);
+            let mut records =
+                runner::solve_problem(problem, parts, input, &input_name, args.timeout);
+            // Inputs outside of the inputs directory have no known answers
+            if let Some(name) = input_source.input_name() {
+                for record in records.iter_mut() {
+                    record.expected = answers.get(day, name, record.part).cloned();
+                }
+            }
+            records
+        },
+        runner::PartRecord::failed,
+    )?;
+    if args.format == OutputFormat::Text && !records.is_empty() {
+        let mut stdout = io::stdout().lock();
+        output::write_summary(&mut stdout, &records)?;
+        if args.all_inputs {
+            output::write_comparison(&mut stdout, &records, &parts)?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn verify(args: &VerifyArgs) -> error::Result<ExitCode> {
+    let answers = Answers::load(args.year)?;
+    let input_dir = match &args.input_dir {
+        Some(dir) => dir.clone(),
+        None => input::get_input_base_path(),
+    };
+    let examples = Examples::load(&input_dir, args.year)?;
+    let parts = args.part.parts();
+
+    let verifications: Vec<_> = runner::select_problems(args.year, args.days.as_ref())
+        .into_iter()
+        // Only the implemented days are verified
+        .filter_map(|problem| problem.ok())
+        .flat_map(|problem| {
+            let mut verifications = verify::verify_problem(&problem, &answers, &parts, &input_dir);
+            verifications.extend(verify::verify_examples(&problem, &examples, &parts));
+            verifications
+        })
+        .collect();
+    verify::write_matrix(&mut io::stdout().lock(), &verifications, &parts)?;
+
+    match verifications.iter().any(|v| v.is_failure()) {
+        true => Ok(ExitCode::FAILURE),
+        false => Ok(ExitCode::SUCCESS),
+    }
+}
+
+fn new_day(args: &NewArgs) -> error::Result<ExitCode> {
+    let title = match &args.title {
+        Some(title) => title.clone(),
+        None => format!("Day {}", args.day),
+    };
+    let input_dir = match &args.input_dir {
+        Some(dir) => dir.clone(),
+        None => input::get_input_base_path(),
+    };
+
+    let touched = scaffold::scaffold_day(
+        Path::new(scaffold::SOURCE_BASE_PATH),
+        &input_dir,
+        args.year,
+        args.day,
+        &title,
+    )?;
+    for path in touched {
+        println!("Wrote {}", path.display());
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn fetch(args: &FetchArgs) -> error::Result<ExitCode> {
+    let client = client::Client::from_env()?;
+    let input_dir = match &args.input_dir {
+        Some(dir) => dir.clone(),
+        None => input::get_input_base_path(),
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+    for &day in args.days.days() {
+        let path = input::get_input_path(&input_dir, args.year, day);
+        match client.download_input(&path, args.year, day) {
+            Ok(true) => println!("Downloaded {}", path.display()),
+            Ok(false) => println!("Cached {}", path.display()),
+            Err(e) => {
+                eprintln!("Error: Day {}: {}", day, e);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+    Ok(exit_code)
+}
+
+fn submit(args: &SubmitArgs) -> error::Result<ExitCode> {
+    let answer = match &args.answer {
+        Some(answer) => Answer::Text(answer.clone()),
+        None => {
+            let problem = Problem::new(args.year, args.day)?;
+            let input = problem.get_default_input()?;
+            problem.get_solution().solve(args.part, &input)?
+        }
+    };
+    let answer = submit::single_answer(answer)?;
+    let history_path = match &args.history {
+        Some(path) => path.clone(),
+        None => submit::get_history_path()?,
+    };
+
+    let client = client::Client::from_env()?;
+    println!(
+        "Submitting {} for {} Day {} Part {}",
+        answer, args.year, args.day, args.part
+    );
+    let verdict = submit::submit(
+        &client,
+        &history_path,
+        args.year,
+        args.day,
+        args.part,
+        &answer,
+    )?;
+    println!("{}", verdict);
+
+    match verdict {
+        submit::Verdict::Correct => Ok(ExitCode::SUCCESS),
+        _ => Ok(ExitCode::FAILURE),
+    }
+}
+
+fn watch(args: &WatchArgs) -> error::Result<ExitCode> {
+    // Fails early on days that are not registered
+    Problem::new(args.year, args.day)?;
+
+    let watch = watch::Watch {
+        year: args.year,
+        day: args.day,
+        part: args.part,
+        source_dir: PathBuf::from(scaffold::SOURCE_BASE_PATH),
+        input_dir: match &args.input_dir {
+            Some(dir) => dir.clone(),
+            None => input::get_input_base_path(),
+        },
+        interval: Duration::from_millis(args.interval),
+    };
+    watch.run()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn inspect(args: &InspectArgs) -> error::Result<ExitCode> {
+    let problem = Problem::new(args.year, args.day)?;
+    let input_source = match (&args.input, &args.input_name, &args.input_dir) {
+        (Some(path), _, _) => InputSource::from(path.as_str()),
+        (_, Some(name), input_dir) => named_input_source(name, input_dir),
+        (None, None, Some(dir)) => InputSource::Dir(dir.clone()),
+        (None, None, None) => InputSource::Default,
+    };
+    let input = problem.get_input(&input_source)?;
+
+    let (year, day) = (args.year, args.day);
+    let dot = problem.get_solution().to_dot(&input)?;
+    let dot = dot.ok_or(SolutionError::NotInspectable { year, day })?;
+    match &args.output {
+        Some(path) => {
+            fs::write(path, dot)?;
+            println!("Wrote {}", path.display());
+        }
+        None => print!("{}", dot),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Parses the command line and runs the selected command, reporting its error on stderr
+pub fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match (&cli.command, &cli.run) {
+        (Some(Command::Verify(args)), _) => verify(args),
+        (Some(Command::New(args)), _) => new_day(args),
+        (Some(Command::Fetch(args)), _) => fetch(args),
+        (Some(Command::Submit(args)), _) => submit(args),
+        (Some(Command::Watch(args)), _) => watch(args),
+        (Some(Command::Inspect(args)), _) => inspect(args),
+        (None, Some(args)) => run(args),
+        (None, None) => {
+            Cli::command()
+                .print_long_help()
+                .expect("Unable to write to stdout");
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
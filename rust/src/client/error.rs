@@ -0,0 +1,23 @@
+pub type Result<T> = core::result::Result<T, ClientError>;
+
+#[derive(thiserror::Error, Debug)]
+#[error("Client Error")]
+pub enum ClientError {
+    #[error(
+        "Missing session token, set {} or `session` in the config file",
+        super::SESSION_ENV
+    )]
+    MissingSession,
+    #[error("Request to {url} failed with status {status}: {message}")]
+    Status {
+        url: String,
+        status: u16,
+        message: String,
+    },
+    #[error("Request to {url} failed: {message}")]
+    Transport { url: String, message: String },
+    #[error("Error parsing config file: {0}")]
+    Config(#[from] toml::de::Error),
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+}
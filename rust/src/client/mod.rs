@@ -0,0 +1,379 @@
+pub mod error;
+
+use error::{ClientError, Result};
+use serde::Deserialize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::solution::Part;
+
+/// Environment variable holding the session token of the Advent of Code website
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Environment variable overriding [`DEFAULT_BASE_URL`]
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+/// Environment variable overriding the path of the config file
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_USER_AGENT: &str = concat!(
+    "github.com/brmejia/aoc ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+/// Minimum time between two requests to the website
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Time of the last request to the website, shared by every client of the process so that the
+/// rate limit holds however many of them are created
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Settings of the config file, all of them optional
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    session: Option<String>,
+    base_url: Option<String>,
+    user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub user_agent: String,
+    pub min_interval: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            min_interval: MIN_REQUEST_INTERVAL,
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Loads the config file, if there is one, with the environment variables taking precedence
+    pub fn load() -> Result<Self> {
+        let file = match get_config_path() {
+            Some(path) => match fs::read_to_string(&path) {
+                Ok(content) => toml::from_str(&content)?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => ConfigFile::default(),
+                Err(e) => return Err(e.into()),
+            },
+            None => ConfigFile::default(),
+        };
+        let defaults = Self::default();
+
+        Ok(Self {
+            base_url: env::var(BASE_URL_ENV)
+                .ok()
+                .or(file.base_url)
+                .unwrap_or(defaults.base_url),
+            session: env::var(SESSION_ENV).ok().or(file.session),
+            user_agent: file.user_agent.unwrap_or(defaults.user_agent),
+            min_interval: defaults.min_interval,
+        })
+    }
+}
+
+/// Path of the config file, taken from [`CONFIG_ENV`] when it is set
+pub fn get_config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_dir.join("aoc").join("config.toml"))
+}
+
+/// Rate limited client of the Advent of Code website
+pub struct Client {
+    config: ClientConfig,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: ClientConfig) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&config.user_agent)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self { config, agent }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(ClientConfig::load()?))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+    }
+
+    fn session(&self) -> Result<&str> {
+        self.config
+            .session
+            .as_deref()
+            .ok_or(ClientError::MissingSession)
+    }
+
+    /// Waits until [`ClientConfig::min_interval`] has passed since the previous request of any
+    /// client. The lock is held while waiting, so concurrent requests go out one at a time.
+    fn throttle(&self) {
+        let mut last_request = LAST_REQUEST.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(last_request) = *last_request {
+            let elapsed = last_request.elapsed();
+            if elapsed < self.config.min_interval {
+                thread::sleep(self.config.min_interval - elapsed);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    /// Reads the body of the response to a request to `url`
+    fn read_response(
+        &self,
+        url: &str,
+        response: core::result::Result<ureq::Response, ureq::Error>,
+    ) -> Result<String> {
+        let response = response.map_err(|e| match e {
+            ureq::Error::Status(status, response) => ClientError::Status {
+                url: url.to_string(),
+                status,
+                message: response
+                    .into_string()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            },
+            ureq::Error::Transport(t) => ClientError::Transport {
+                url: url.to_string(),
+                message: [t.kind().to_string()]
+                    .into_iter()
+                    .chain(t.message().map(String::from))
+                    .chain(std::error::Error::source(&t).map(|e| e.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(": "),
+            },
+        })?;
+        Ok(response.into_string()?)
+    }
+
+    /// Authenticated request to a page of the website, throttled to be sent right away
+    fn request(&self, method: &str, path: &str) -> Result<(String, ureq::Request)> {
+        let url = self.url(path);
+        let request = self
+            .agent
+            .request(method, &url)
+            .set("Cookie", &format!("session={}", self.session()?));
+        self.throttle();
+        Ok((url, request))
+    }
+
+    fn get(&self, path: &str) -> Result<String> {
+        let (url, request) = self.request("GET", path)?;
+        self.read_response(&url, request.call())
+    }
+
+    fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        let (url, request) = self.request("POST", path)?;
+        self.read_response(&url, request.send_form(form))
+    }
+
+    pub fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// Posts the answer of a part, returning the page with the verdict
+    pub fn submit_answer(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<String> {
+        let level = (part as u8).to_string();
+        self.post_form(
+            &format!("/{}/day/{}/answer", year, day),
+            &[("level", &level), ("answer", answer)],
+        )
+    }
+
+    /// Downloads the input to `path` unless it is already there. Empty files, like the placeholders
+    /// written by `aoc new`, do not count as downloaded.
+    ///
+    /// Returns whether the input was downloaded.
+    pub fn download_input(&self, path: &Path, year: u16, day: u8) -> Result<bool> {
+        if is_cached(path) {
+            return Ok(false);
+        }
+        let input = self.fetch_input(year, day)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Written aside first so an interrupted download never looks cached
+        let partial_path = path.with_extension("part");
+        fs::write(&partial_path, input)?;
+        fs::rename(&partial_path, path)?;
+        Ok(true)
+    }
+
+    /// Reads the input at `path`, downloading it first when it is missing
+    pub fn cached_input(&self, path: &Path, year: u16, day: u8) -> Result<String> {
+        self.download_input(path, year, day)?;
+        Ok(fs::read_to_string(path)?)
+    }
+}
+
+fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    use super::*;
+
+    /// Serves the given `(status, body)` responses, one per connection, and sends every request
+    /// received through the returned channel
+    pub fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                sender.send(request).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (base_url, receiver)
+    }
+
+    pub fn test_client(base_url: String) -> Client {
+        Client::new(ClientConfig {
+            base_url,
+            session: Some("53cr37".to_string()),
+            min_interval: Duration::from_millis(200),
+            ..ClientConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let (base_url, requests) = mock_server(vec![(200, "(()(()(\n")]);
+        let client = test_client(base_url);
+
+        assert_eq!(client.fetch_input(2015, 1).unwrap(), "(()(()(\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2015/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=53cr37\r\n"));
+        assert!(request.contains(&format!("User-Agent: {}\r\n", DEFAULT_USER_AGENT)));
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let (base_url, requests) = mock_server(vec![(200, "<article>ok</article>")]);
+        let client = test_client(base_url);
+
+        let page = client.submit_answer(2015, 4, Part::Two, "9962624").unwrap();
+        assert_eq!(page, "<article>ok</article>");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2015/day/4/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=53cr37\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=9962624"));
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, _requests) = mock_server(vec![(404, "Not Found\n")]);
+        let client = test_client(base_url);
+
+        match client.fetch_input(2015, 26) {
+            Err(ClientError::Status {
+                status, message, ..
+            }) => assert_eq!((status, message.as_str()), (404, "Not Found")),
+            r => panic!("Should have been a status error, got {r:?}"),
+        }
+
+        let client = Client::new(ClientConfig::default());
+        assert!(matches!(
+            client.fetch_input(2015, 1),
+            Err(ClientError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn test_cached_input_is_downloaded_once() {
+        let dir = env::temp_dir().join(format!("aoc-client-{}", std::process::id()));
+        let path = dir.join("2015").join("day3.txt");
+        let _ = fs::remove_dir_all(&dir);
+
+        let (base_url, requests) = mock_server(vec![(200, "^>v<"), (200, ">")]);
+        let client = test_client(base_url);
+
+        assert_eq!(client.cached_input(&path, 2015, 3).unwrap(), "^>v<");
+        assert_eq!(client.cached_input(&path, 2015, 3).unwrap(), "^>v<");
+        assert!(!client.download_input(&path, 2015, 3).unwrap());
+        assert_eq!(requests.try_iter().count(), 1);
+
+        // Empty placeholders do not count as downloaded
+        fs::write(&path, "").unwrap();
+        assert!(client.download_input(&path, 2015, 3).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), ">");
+        assert_eq!(requests.try_iter().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_throttle() {
+        let client = test_client("http://localhost".into());
+        let other_client = test_client("http://localhost".into());
+
+        let current = Instant::now();
+        client.throttle();
+        other_client.throttle();
+        client.throttle();
+        assert!(current.elapsed() >= Duration::from_millis(400));
+    }
+}
@@ -1,6 +1,8 @@
-#![allow(dead_code)]
-
-use crate::{input::error::InputError, solution::error::SolutionError};
+use crate::{
+    answers::error::AnswersError, client::error::ClientError, examples::error::ExamplesError,
+    input::error::InputError, scaffold::error::ScaffoldError, solution::error::SolutionError,
+    submit::error::SubmitError,
+};
 
 pub type Result<T> = core::result::Result<T, AoCError>;
 
@@ -12,5 +14,15 @@ pub enum AoCError {
     #[error(transparent)]
     Input(#[from] InputError),
     #[error(transparent)]
+    Answers(#[from] AnswersError),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error(transparent)]
+    Examples(#[from] ExamplesError),
+    #[error(transparent)]
+    Scaffold(#[from] ScaffoldError),
+    #[error(transparent)]
+    Submit(#[from] SubmitError),
+    #[error(transparent)]
     IO(#[from] std::io::Error),
 }
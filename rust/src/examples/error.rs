@@ -0,0 +1,16 @@
+use crate::answers::error::AnswersError;
+
+pub type Result<T> = core::result::Result<T, ExamplesError>;
+
+#[derive(thiserror::Error, Debug)]
+#[error("Examples Error")]
+pub enum ExamplesError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("Error loading the answers of the examples: {0}")]
+    Answers(#[from] AnswersError),
+    #[error("Day {day} of {year} has no example named `{name}`")]
+    NotFound { year: u16, day: u8, name: String },
+    #[error("Day {day} of {year} has no examples")]
+    NoExamples { year: u16, day: u8 },
+}
@@ -0,0 +1,220 @@
+pub mod error;
+
+use error::{ExamplesError, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answers::Answers,
+    solution::{Part, answer::Answer},
+};
+
+/// Directory of the examples inside the inputs directory of a year
+const EXAMPLES_DIR_NAME: &str = "examples";
+/// Expected answers of the examples of a year, in the format of the answers files
+const EXAMPLE_ANSWERS_FILE_NAME: &str = "answers.toml";
+
+/// Example input of a day, stored as `{year}/examples/day{day}_{name}.txt` in the inputs directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Example {
+    pub fn read(&self) -> Result<String> {
+        Ok(fs::read_to_string(&self.path)?)
+    }
+}
+
+/// Example inputs of a year along with their expected answers, keyed by day, example name and part
+#[derive(Debug, Clone)]
+pub struct Examples {
+    year: u16,
+    dir: PathBuf,
+    answers: Answers,
+}
+
+impl Examples {
+    /// Loads the examples of the year from the given inputs directory. A missing examples
+    /// directory means there are no examples.
+    pub fn load(input_dir: &Path, year: u16) -> Result<Self> {
+        let dir = get_examples_path(input_dir, year);
+        let answers = Answers::load_file(&dir.join(EXAMPLE_ANSWERS_FILE_NAME))?;
+        Ok(Self { year, dir, answers })
+    }
+
+    /// Examples of the day, sorted by name
+    pub fn of_day(&self, day: u8) -> Result<Vec<Example>> {
+        let prefix = format!("day{}_", day);
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut examples = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(|name| name.strip_suffix(".txt"))
+                .map(String::from);
+            if let Some(name) = name {
+                examples.push(Example { day, name, path });
+            }
+        }
+        examples.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(examples)
+    }
+
+    pub fn get(&self, day: u8, name: &str) -> Result<Example> {
+        let path = self.dir.join(format!("day{}_{}.txt", day, name));
+        match path.is_file() {
+            true => Ok(Example {
+                day,
+                name: name.to_string(),
+                path,
+            }),
+            false => Err(ExamplesError::NotFound {
+                year: self.year,
+                day,
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// The named example of the day, or all of them when no name is given
+    pub fn select(&self, day: u8, name: Option<&str>) -> Result<Vec<Example>> {
+        match name {
+            Some(name) => Ok(vec![self.get(day, name)?]),
+            None => match self.of_day(day)? {
+                examples if examples.is_empty() => Err(ExamplesError::NoExamples {
+                    year: self.year,
+                    day,
+                }),
+                examples => Ok(examples),
+            },
+        }
+    }
+
+    pub fn expected(&self, example: &Example, part: Part) -> Option<&Answer> {
+        self.answers.get(example.day, &example.name, part)
+    }
+}
+
+/// Directory holding the examples of a year inside the given inputs directory
+pub fn get_examples_path(input_dir: impl AsRef<Path>, year: u16) -> PathBuf {
+    input_dir
+        .as_ref()
+        .join(year.to_string())
+        .join(EXAMPLES_DIR_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        input,
+        pow::Miner,
+        solution::{self, Solution, aoc2015::Day4},
+    };
+
+    #[test]
+    fn test_load_examples() {
+        let root = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let dir = get_examples_path(&root, 2015);
+        let _ = fs::remove_dir_all(&root);
+
+        let examples = Examples::load(&root, 2015).unwrap();
+        assert!(examples.of_day(3).unwrap().is_empty());
+        assert!(matches!(
+            examples.select(3, None),
+            Err(ExamplesError::NoExamples { day: 3, .. })
+        ));
+
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "day3_b.txt",
+            "day3_a.txt",
+            "day30_a.txt",
+            "day3.txt",
+            "answers.toml",
+        ] {
+            fs::write(dir.join(name), "^v").unwrap();
+        }
+        fs::write(dir.join("answers.toml"), "[day3.a]\npart2 = 3").unwrap();
+
+        let examples = Examples::load(&root, 2015).unwrap();
+        let names: Vec<String> = examples
+            .of_day(3)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+
+        let example = examples.get(3, "a").unwrap();
+        assert_eq!(example.path, dir.join("day3_a.txt"));
+        assert_eq!(example.read().unwrap(), "^v");
+        assert_eq!(examples.expected(&example, Part::One), None);
+        assert_eq!(
+            examples.expected(&example, Part::Two),
+            Some(&Answer::Integer(3))
+        );
+        assert_eq!(examples.select(3, Some("b")).unwrap().len(), 1);
+        assert!(matches!(
+            examples.get(3, "c"),
+            Err(ExamplesError::NotFound { day: 3, .. })
+        ));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    /// Solves every example with known answers with the solution of its day
+    #[test]
+    fn test_registered_examples() {
+        let input_dir = input::get_input_base_path();
+        let mut examples_by_year = BTreeMap::new();
+        let mut failures = Vec::new();
+        let mut checked = 0;
+
+        for info in solution::registry() {
+            let examples = examples_by_year
+                .entry(info.year)
+                .or_insert_with(|| Examples::load(&input_dir, info.year).unwrap());
+            // Day 4 would leave the checkpoints of its searches behind
+            let solution: Box<dyn Solution> = match (info.year, info.day) {
+                (2015, 4) => Box::new(Day4::with_miner(Miner::new().with_checkpoint_dir(None))),
+                _ => (info.build)(),
+            };
+
+            for example in examples.of_day(info.day).unwrap() {
+                let input = example.read().unwrap();
+                for part in [Part::One, Part::Two] {
+                    let Some(expected) = examples.expected(&example, part) else {
+                        continue;
+                    };
+                    checked += 1;
+                    match solution.solve(part, &input) {
+                        Ok(answer) if answer == *expected => {}
+                        result => failures.push(format!(
+                            "{} day {} example {} part {}: expected {:?}, got {:?}",
+                            info.year, info.day, example.name, part, expected, result
+                        )),
+                    }
+                }
+            }
+        }
+
+        assert!(checked > 0, "No examples with known answers");
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
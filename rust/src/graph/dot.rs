@@ -0,0 +1,161 @@
+use std::fmt::Display;
+
+use super::{Direction, Graph, VertexId};
+
+/// Graphviz attributes of a vertex, an edge or a path, as name and value pairs
+pub type Attributes = Vec<(&'static str, String)>;
+
+type VertexAttributes<'a, V> = Box<dyn Fn(VertexId, &V) -> Attributes + 'a>;
+type EdgeAttributes<'a, E> = Box<dyn Fn(VertexId, VertexId, &E) -> Attributes + 'a>;
+
+/// Graphviz DOT rendering of a graph, written through its [`Display`] implementation.
+/// Vertexes are named by their ids, so their labels have to be set to show anything else.
+pub struct Dot<'a, V, E, D> {
+    graph: &'a Graph<V, E, D>,
+    name: String,
+    attributes: Attributes,
+    vertex_attributes: VertexAttributes<'a, V>,
+    edge_attributes: EdgeAttributes<'a, E>,
+    /// Highlighted paths, drawn over the edges of the graph
+    paths: Vec<(Vec<VertexId>, Attributes)>,
+}
+
+impl<'a, V, E, D: Direction> Dot<'a, V, E, D> {
+    pub fn new(graph: &'a Graph<V, E, D>, name: &str) -> Self {
+        Self {
+            graph,
+            name: name.to_string(),
+            attributes: Vec::new(),
+            vertex_attributes: Box::new(|_, _| Vec::new()),
+            edge_attributes: Box::new(|_, _, _| Vec::new()),
+            paths: Vec::new(),
+        }
+    }
+
+    /// Attributes of the whole graph, such as its `label`
+    pub fn with_attributes(self, attributes: Attributes) -> Self {
+        Self { attributes, ..self }
+    }
+
+    pub fn with_vertex_attributes(self, f: impl Fn(VertexId, &V) -> Attributes + 'a) -> Self {
+        Self {
+            vertex_attributes: Box::new(f),
+            ..self
+        }
+    }
+
+    pub fn with_edge_attributes(
+        self,
+        f: impl Fn(VertexId, VertexId, &E) -> Attributes + 'a,
+    ) -> Self {
+        Self {
+            edge_attributes: Box::new(f),
+            ..self
+        }
+    }
+
+    /// Draws the steps of the path as extra edges with the given attributes
+    pub fn with_path(mut self, path: &[VertexId], attributes: Attributes) -> Self {
+        self.paths.push((path.to_vec(), attributes));
+        self
+    }
+}
+
+impl<V, E, D: Direction> Display for Dot<'_, V, E, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (kind, arrow) = match self.graph.is_directed() {
+            true => ("digraph", "->"),
+            false => ("graph", "--"),
+        };
+        writeln!(f, "{} {} {{", kind, quote(&self.name))?;
+        for (name, value) in &self.attributes {
+            writeln!(f, "    {}={};", name, quote(value))?;
+        }
+        for (id, vertex) in self.graph.vertexes() {
+            let attributes = (self.vertex_attributes)(id, vertex);
+            writeln!(f, "    {}{};", id, AttributeList(&attributes))?;
+        }
+        for (from, to, weight) in self.graph.edges() {
+            let attributes = (self.edge_attributes)(from, to, weight);
+            let attributes = AttributeList(&attributes);
+            writeln!(f, "    {} {} {}{};", from, arrow, to, attributes)?;
+        }
+        for (path, attributes) in &self.paths {
+            for step in path.windows(2) {
+                let attributes = AttributeList(attributes);
+                writeln!(f, "    {} {} {}{};", step[0], arrow, step[1], attributes)?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Attributes written as ` [name="value", ...]`, or nothing when there are none
+struct AttributeList<'a>(&'a Attributes);
+
+impl Display for AttributeList<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let attributes: Vec<_> = self
+            .0
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, quote(value)))
+            .collect();
+        write!(f, " [{}]", attributes.join(", "))
+    }
+}
+
+/// DOT string literal of the text
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DiGraph, UnGraph};
+
+    #[test]
+    fn test_undirected_dot() {
+        let mut graph = UnGraph::new();
+        graph.add_edge("a", "b", 3);
+        graph.add_edge("b", "c \"quoted\"", 5);
+
+        let dot = Dot::new(&graph, "cities")
+            .with_attributes(vec![("label", "Route\nof 8".into())])
+            .with_vertex_attributes(|_, name| vec![("label", name.to_string())])
+            .with_edge_attributes(|_, _, weight| vec![("label", weight.to_string())])
+            .with_path(&[0, 1, 2], vec![("color", "red".into())]);
+
+        let expected = [
+            "graph \"cities\" {",
+            "    label=\"Route\\nof 8\";",
+            "    0 [label=\"a\"];",
+            "    1 [label=\"b\"];",
+            "    2 [label=\"c \\\"quoted\\\"\"];",
+            "    0 -- 1 [label=\"3\"];",
+            "    1 -- 2 [label=\"5\"];",
+            "    0 -- 1 [color=\"red\"];",
+            "    1 -- 2 [color=\"red\"];",
+            "}",
+            "",
+        ];
+        assert_eq!(dot.to_string(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_directed_dot() {
+        let mut graph = DiGraph::new();
+        graph.add_edge(1, 2, ());
+        graph.add_edge(2, 1, ());
+
+        let expected = "digraph \"loop\" {\n    0;\n    1;\n    0 -> 1;\n    1 -> 0;\n}\n";
+        assert_eq!(Dot::new(&graph, "loop").to_string(), expected);
+    }
+}
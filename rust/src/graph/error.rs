@@ -0,0 +1,8 @@
+pub type Result<T> = core::result::Result<T, GraphError>;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("Graph Error")]
+pub enum GraphError {
+    #[error("The graph has a cycle through vertex {vertex}, so it has no topological order")]
+    Cycle { vertex: usize },
+}
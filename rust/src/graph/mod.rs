@@ -0,0 +1,221 @@
+//! Weighted graphs over any hashable vertex type, directed or undirected.
+//!
+//! Vertexes are interned: adding one returns a dense [`VertexId`] that every algorithm works
+//! with, and [`Graph::vertex`] turns back into the vertex.
+
+pub mod dot;
+pub mod error;
+pub mod order;
+pub mod search;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Add,
+};
+
+/// Index of a vertex in its graph, given in the order vertexes are added
+pub type VertexId = usize;
+
+/// Whether edges go one way or both ways
+pub trait Direction {
+    const DIRECTED: bool;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Directed;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Undirected;
+
+impl Direction for Directed {
+    const DIRECTED: bool = true;
+}
+
+impl Direction for Undirected {
+    const DIRECTED: bool = false;
+}
+
+/// Edge weights the shortest path algorithms can add up. The default value is the zero weight,
+/// and weights are expected not to be negative.
+pub trait Weight: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Weight for T {}
+
+#[derive(Clone, Debug)]
+pub struct Graph<V, E, D = Undirected> {
+    vertexes: Vec<V>,
+    ids: HashMap<V, VertexId>,
+    /// Outgoing edges of each vertex. Undirected edges are stored in both of their ends.
+    edges: Vec<BTreeMap<VertexId, E>>,
+    direction: PhantomData<D>,
+}
+
+pub type DiGraph<V, E> = Graph<V, E, Directed>;
+pub type UnGraph<V, E> = Graph<V, E, Undirected>;
+
+impl<V, E, D> Default for Graph<V, E, D> {
+    fn default() -> Self {
+        Self {
+            vertexes: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+            direction: PhantomData,
+        }
+    }
+}
+
+impl<V: Clone + Eq + Hash, E, D: Direction> Graph<V, E, D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the vertex, adding it when it is not in the graph yet
+    pub fn add_vertex(&mut self, vertex: V) -> VertexId {
+        if let Some(&id) = self.ids.get(&vertex) {
+            return id;
+        }
+        let id = self.vertexes.len();
+        self.vertexes.push(vertex.clone());
+        self.ids.insert(vertex, id);
+        self.edges.push(BTreeMap::new());
+        id
+    }
+
+    /// Adds the edge along with any of its vertexes not in the graph yet, replacing the weight
+    /// of an existing edge between them. Returns the ids of both ends.
+    pub fn add_edge(&mut self, from: V, to: V, weight: E) -> (VertexId, VertexId)
+    where
+        E: Clone,
+    {
+        let (from, to) = (self.add_vertex(from), self.add_vertex(to));
+        self.add_edge_by_id(from, to, weight);
+        (from, to)
+    }
+
+    /// Same as [`Graph::add_edge`] between vertexes already in the graph
+    ///
+    /// Panics when either id is not in the graph.
+    pub fn add_edge_by_id(&mut self, from: VertexId, to: VertexId, weight: E)
+    where
+        E: Clone,
+    {
+        assert!(to < self.len(), "Vertex {to} is not in the graph");
+        if !D::DIRECTED {
+            self.edges[to].insert(from, weight.clone());
+        }
+        self.edges[from].insert(to, weight);
+    }
+
+    pub fn id(&self, vertex: &V) -> Option<VertexId> {
+        self.ids.get(vertex).copied()
+    }
+}
+
+impl<V, E, D: Direction> Graph<V, E, D> {
+    pub fn is_directed(&self) -> bool {
+        D::DIRECTED
+    }
+
+    /// Number of vertexes
+    pub fn len(&self) -> usize {
+        self.vertexes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertexes.is_empty()
+    }
+
+    /// Panics when the id is not in the graph
+    pub fn vertex(&self, id: VertexId) -> &V {
+        &self.vertexes[id]
+    }
+
+    pub fn vertexes(&self) -> impl Iterator<Item = (VertexId, &V)> {
+        self.vertexes.iter().enumerate()
+    }
+
+    pub fn vertex_ids(&self) -> std::ops::Range<VertexId> {
+        0..self.len()
+    }
+
+    /// Weight of the edge going from one vertex to the other, if any
+    pub fn edge(&self, from: VertexId, to: VertexId) -> Option<&E> {
+        self.edges.get(from)?.get(&to)
+    }
+
+    /// Vertexes at the end of the outgoing edges of a vertex, in id order, with their weights
+    ///
+    /// Panics when the id is not in the graph.
+    pub fn neighbors(&self, id: VertexId) -> impl Iterator<Item = (VertexId, &E)> {
+        self.edges[id].iter().map(|(&to, weight)| (to, weight))
+    }
+
+    /// Every edge, once, as its ends and weight. Undirected edges go from the lower id.
+    pub fn edges(&self) -> impl Iterator<Item = (VertexId, VertexId, &E)> {
+        self.edges.iter().enumerate().flat_map(|(from, edges)| {
+            edges
+                .iter()
+                .filter(move |&(&to, _)| D::DIRECTED || from <= to)
+                .map(move |(&to, weight)| (from, to, weight))
+        })
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges().count()
+    }
+}
+
+/// Path found by a shortest path search
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<E> {
+    /// Vertexes from the start to the end of the path, both included
+    pub vertexes: Vec<VertexId>,
+    pub distance: E,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interned_vertexes() {
+        let mut graph = UnGraph::new();
+        assert_eq!(graph.add_edge("a", "b", 3), (0, 1));
+        assert_eq!(graph.add_edge("c", "a", 5), (2, 0));
+        assert_eq!(graph.add_vertex("b"), 1);
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.id(&"c"), Some(2));
+        assert_eq!(graph.id(&"d"), None);
+        assert_eq!(*graph.vertex(1), "b");
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut undirected = UnGraph::new();
+        let mut directed = DiGraph::new();
+        for (from, to, weight) in [("a", "b", 3), ("c", "a", 5), ("a", "b", 4)] {
+            undirected.add_edge(from, to, weight);
+            directed.add_edge(from, to, weight);
+        }
+
+        assert_eq!(undirected.edge(1, 0), Some(&4));
+        assert_eq!(directed.edge(1, 0), None);
+        assert_eq!(
+            undirected.edges().collect::<Vec<_>>(),
+            vec![(0, 1, &4), (0, 2, &5)]
+        );
+        assert_eq!(
+            directed.edges().collect::<Vec<_>>(),
+            vec![(0, 1, &4), (2, 0, &5)]
+        );
+        assert_eq!(
+            undirected.neighbors(0).collect::<Vec<_>>(),
+            vec![(1, &4), (2, &5)]
+        );
+        assert_eq!(directed.neighbors(0).collect::<Vec<_>>(), vec![(1, &4)]);
+        assert_eq!((undirected.edge_count(), directed.edge_count()), (2, 2));
+    }
+}
@@ -0,0 +1,177 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::{Directed, Graph, VertexId, error::GraphError, error::Result};
+
+impl<V, E> Graph<V, E, Directed> {
+    /// Vertexes ordered so that every edge goes from an earlier vertex to a later one, taking the
+    /// lowest id first whenever there is a choice
+    pub fn toposort(&self) -> Result<Vec<VertexId>> {
+        let mut incoming = vec![0usize; self.len()];
+        for (_, to, _) in self.edges() {
+            incoming[to] += 1;
+        }
+
+        let mut ready: BinaryHeap<_> = self
+            .vertex_ids()
+            .filter(|&id| incoming[id] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(Reverse(id)) = ready.pop() {
+            order.push(id);
+            for (next, _) in self.neighbors(id) {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        // Vertexes left have an edge from another vertex left, so walking those edges back
+        // ends up going round a cycle
+        let left = |id: VertexId| incoming[id] > 0;
+        let Some(mut vertex) = self.vertex_ids().find(|&id| left(id)) else {
+            return Ok(order);
+        };
+        let mut seen = vec![false; self.len()];
+        while !seen[vertex] {
+            seen[vertex] = true;
+            vertex = self
+                .edges()
+                .find(|&(from, to, _)| to == vertex && left(from))
+                .map(|(from, _, _)| from)
+                .unwrap();
+        }
+        Err(GraphError::Cycle { vertex })
+    }
+
+    /// Groups of vertexes that can all reach each other, found with Tarjan's algorithm. Every
+    /// component comes after the components it has edges to.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<VertexId>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let n = self.len();
+        let mut index = vec![UNVISITED; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut visited = 0;
+
+        for root in self.vertex_ids() {
+            if index[root] != UNVISITED {
+                continue;
+            }
+            // Iterative depth-first search, each frame holding a vertex and its neighbors left
+            let mut frames = vec![(root, self.neighbors(root))];
+            index[root] = visited;
+            lowlink[root] = visited;
+            visited += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((id, neighbors)) = frames.last_mut() {
+                let id = *id;
+                if let Some((next, _)) = neighbors.next() {
+                    if index[next] == UNVISITED {
+                        index[next] = visited;
+                        lowlink[next] = visited;
+                        visited += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        frames.push((next, self.neighbors(next)));
+                    } else if on_stack[next] {
+                        lowlink[id] = lowlink[id].min(index[next]);
+                    }
+                    continue;
+                }
+
+                frames.pop();
+                if let Some((parent, _)) = frames.last() {
+                    lowlink[*parent] = lowlink[*parent].min(lowlink[id]);
+                }
+                if lowlink[id] == index[id] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == id {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::DiGraph;
+
+    fn graph(edges: &[(u8, u8)]) -> DiGraph<u8, ()> {
+        let mut graph = DiGraph::new();
+        for &(from, to) in edges {
+            graph.add_edge(from, to, ());
+        }
+        graph
+    }
+
+    fn vertexes(graph: &DiGraph<u8, ()>, ids: &[VertexId]) -> Vec<u8> {
+        ids.iter().map(|&id| *graph.vertex(id)).collect()
+    }
+
+    #[test]
+    fn test_toposort() {
+        let dag = graph(&[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]);
+        let order = dag.toposort().unwrap();
+        assert_eq!(vertexes(&dag, &order), vec![5, 2, 4, 0, 3, 1]);
+        for (from, to, _) in dag.edges() {
+            let position = |id| order.iter().position(|&v| v == id).unwrap();
+            assert!(position(from) < position(to));
+        }
+
+        let validations = vec![
+            (graph(&[(1, 2), (2, 3), (3, 2), (3, 4)]), 2),
+            // Vertex 1 only hangs off the cycle
+            (graph(&[(1, 2), (3, 4), (4, 3), (4, 1)]), 4),
+            (graph(&[(7, 7)]), 7),
+        ];
+
+        for (graph, expected_result) in validations.into_iter() {
+            match graph.toposort() {
+                Err(GraphError::Cycle { vertex }) => {
+                    assert_eq!(*graph.vertex(vertex), expected_result)
+                }
+                r => panic!("Should have been a cycle, got {r:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let graph = graph(&[
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 4),
+            (7, 6),
+            (7, 8),
+            (8, 7),
+        ]);
+        let components: Vec<_> = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|component| vertexes(&graph, component))
+            .collect();
+
+        assert_eq!(components, vec![vec![4, 5, 6], vec![1, 2, 3], vec![7, 8]]);
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use super::{Direction, Graph, Path, VertexId, Weight};
+
+/// Distances from a vertex to every other, as found by [`Graph::dijkstra`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortestPaths<E> {
+    distances: Vec<Option<E>>,
+    /// Vertex before each one on its shortest path
+    previous: Vec<Option<VertexId>>,
+}
+
+impl<E: Weight> ShortestPaths<E> {
+    /// Distance to the vertex, unless it cannot be reached
+    pub fn distance(&self, to: VertexId) -> Option<E> {
+        self.distances.get(to).copied().flatten()
+    }
+
+    pub fn path(&self, to: VertexId) -> Option<Path<E>> {
+        let distance = self.distance(to)?;
+        let mut vertexes = vec![to];
+        while let Some(previous) = self.previous[*vertexes.last().unwrap()] {
+            vertexes.push(previous);
+        }
+        vertexes.reverse();
+        Some(Path { vertexes, distance })
+    }
+}
+
+impl<V, E, D: Direction> Graph<V, E, D> {
+    /// Vertexes reachable from `start`, in breadth-first order
+    pub fn bfs(&self, start: VertexId) -> Vec<VertexId> {
+        let mut visited = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for (next, _) in self.neighbors(id) {
+                if !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// Vertexes reachable from `start`, in depth-first preorder
+    pub fn dfs(&self, start: VertexId) -> Vec<VertexId> {
+        let mut visited = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            order.push(id);
+            // Pushed in reverse so that neighbors are visited in id order
+            let neighbors: Vec<_> = self.neighbors(id).map(|(next, _)| next).collect();
+            stack.extend(neighbors.into_iter().rev().filter(|&next| !visited[next]));
+        }
+        order
+    }
+}
+
+impl<V, E: Weight, D: Direction> Graph<V, E, D> {
+    /// Shortest paths from `start` to every vertex
+    pub fn dijkstra(&self, start: VertexId) -> ShortestPaths<E> {
+        let mut paths = ShortestPaths {
+            distances: vec![None; self.len()],
+            previous: vec![None; self.len()],
+        };
+        paths.distances[start] = Some(E::default());
+        let mut queue = BinaryHeap::from([Reverse((E::default(), start))]);
+        while let Some(Reverse((distance, id))) = queue.pop() {
+            if paths.distances[id].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for (next, &weight) in self.neighbors(id) {
+                let candidate = distance + weight;
+                if paths.distances[next].is_none_or(|best| candidate < best) {
+                    paths.distances[next] = Some(candidate);
+                    paths.previous[next] = Some(id);
+                    queue.push(Reverse((candidate, next)));
+                }
+            }
+        }
+        paths
+    }
+
+    /// Shortest path from `start` to `goal`, guided by a `heuristic` estimate of the distance
+    /// from each vertex to the goal. The path is the shortest as long as the heuristic never
+    /// overestimates it.
+    pub fn astar(
+        &self,
+        start: VertexId,
+        goal: VertexId,
+        heuristic: impl Fn(VertexId) -> E,
+    ) -> Option<Path<E>> {
+        let mut distances: Vec<Option<E>> = vec![None; self.len()];
+        let mut previous = vec![None; self.len()];
+        distances[start] = Some(E::default());
+        let mut queue = BinaryHeap::from([Reverse((heuristic(start), start))]);
+        while let Some(Reverse((_, id))) = queue.pop() {
+            let distance = distances[id]?;
+            if id == goal {
+                let mut vertexes = vec![goal];
+                while let Some(before) = previous[*vertexes.last().unwrap()] {
+                    vertexes.push(before);
+                }
+                vertexes.reverse();
+                return Some(Path { vertexes, distance });
+            }
+            for (next, &weight) in self.neighbors(id) {
+                let candidate = distance + weight;
+                if distances[next].is_none_or(|best| candidate < best) {
+                    distances[next] = Some(candidate);
+                    previous[next] = Some(id);
+                    queue.push(Reverse((candidate + heuristic(next), next)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Distance between every pair of vertexes, indexed by their ids, found with the
+    /// Floyd–Warshall algorithm
+    pub fn all_pairs_shortest_paths(&self) -> Vec<Vec<Option<E>>> {
+        let n = self.len();
+        let mut distances = vec![vec![None; n]; n];
+        for (from, row) in distances.iter_mut().enumerate() {
+            row[from] = Some(E::default());
+            for (to, &weight) in self.neighbors(from) {
+                if row[to].is_none_or(|best| weight < best) {
+                    row[to] = Some(weight);
+                }
+            }
+        }
+        for through in 0..n {
+            let onwards = distances[through].clone();
+            for row in distances.iter_mut() {
+                let Some(first) = row[through] else {
+                    continue;
+                };
+                for (best, second) in row.iter_mut().zip(&onwards) {
+                    if let Some(second) = *second
+                        && best.is_none_or(|best| first + second < best)
+                    {
+                        *best = Some(first + second);
+                    }
+                }
+            }
+        }
+        distances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DiGraph, UnGraph};
+
+    /// Points of a grid with the Manhattan distance between neighbors, walls left out
+    fn grid(rows: &[&str]) -> UnGraph<(i32, i32), u32> {
+        let mut graph = UnGraph::new();
+        let open = |x: i32, y: i32| {
+            rows.get(y as usize)
+                .and_then(|row| row.as_bytes().get(x as usize))
+                .is_some_and(|&c| c == b'.')
+        };
+        for (y, row) in rows.iter().enumerate() {
+            for x in 0..row.len() as i32 {
+                let y = y as i32;
+                if !open(x, y) {
+                    continue;
+                }
+                graph.add_vertex((x, y));
+                for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                    if open(nx, ny) {
+                        graph.add_edge((x, y), (nx, ny), 1);
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    fn roads() -> DiGraph<&'static str, u32> {
+        let mut graph = DiGraph::new();
+        for (from, to, weight) in [
+            ("a", "b", 7),
+            ("a", "c", 9),
+            ("a", "f", 14),
+            ("b", "c", 10),
+            ("b", "d", 15),
+            ("c", "d", 11),
+            ("c", "f", 2),
+            ("d", "e", 6),
+            ("f", "e", 9),
+        ] {
+            graph.add_edge(from, to, weight);
+        }
+        graph.add_vertex("g");
+        graph
+    }
+
+    #[test]
+    fn test_bfs_and_dfs() {
+        let graph = roads();
+        let names = |ids: Vec<VertexId>| {
+            ids.into_iter()
+                .map(|id| *graph.vertex(id))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(graph.bfs(0)), vec!["a", "b", "c", "f", "d", "e"]);
+        // Neighbors come in id order, and `f` was added before `d`
+        assert_eq!(names(graph.dfs(0)), vec!["a", "b", "c", "f", "e", "d"]);
+        assert_eq!(names(graph.bfs(graph.id(&"d").unwrap())), vec!["d", "e"]);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let graph = roads();
+        let paths = graph.dijkstra(0);
+        let validations = vec![
+            ("a", Some(0), vec!["a"]),
+            ("c", Some(9), vec!["a", "c"]),
+            ("d", Some(20), vec!["a", "c", "d"]),
+            ("e", Some(20), vec!["a", "c", "f", "e"]),
+            ("g", None, vec![]),
+        ];
+
+        for (to, expected_distance, expected_path) in validations.into_iter() {
+            let to = graph.id(&to).unwrap();
+            assert_eq!(paths.distance(to), expected_distance);
+            let path = paths.path(to).map(|p| p.vertexes).unwrap_or_default();
+            let path: Vec<_> = path.into_iter().map(|id| *graph.vertex(id)).collect();
+            assert_eq!(path, expected_path);
+        }
+    }
+
+    #[test]
+    fn test_astar() {
+        let graph = grid(&[
+            ".....", //
+            ".###.", //
+            "...#.", //
+            ".#...", //
+        ]);
+        let start = graph.id(&(0, 0)).unwrap();
+        let goal = graph.id(&(2, 3)).unwrap();
+        let manhattan = |id: VertexId| {
+            let (x, y) = *graph.vertex(id);
+            x.abs_diff(2) + y.abs_diff(3)
+        };
+
+        let path = graph.astar(start, goal, manhattan).unwrap();
+        assert_eq!(path.distance, 5);
+        assert_eq!(path.vertexes.len(), 6);
+        assert_eq!(Some(path.distance), graph.dijkstra(start).distance(goal));
+        // Without a heuristic it is Dijkstra's algorithm
+        assert_eq!(graph.astar(start, goal, |_| 0).unwrap().distance, 5);
+
+        let walled = grid(&[".#."]);
+        let (start, goal) = (walled.id(&(0, 0)).unwrap(), walled.id(&(2, 0)).unwrap());
+        assert_eq!(walled.astar(start, goal, |_| 0), None);
+    }
+
+    #[test]
+    fn test_all_pairs_shortest_paths() {
+        let graph = roads();
+        let distances = graph.all_pairs_shortest_paths();
+
+        for from in graph.vertex_ids() {
+            let paths = graph.dijkstra(from);
+            for to in graph.vertex_ids() {
+                assert_eq!(distances[from][to], paths.distance(to));
+            }
+        }
+    }
+}
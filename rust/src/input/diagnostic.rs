@@ -0,0 +1,284 @@
+use std::{fmt::Display, ops::Range};
+
+use nom::error::{ContextError, ErrorKind, ParseError};
+use serde::Serialize;
+
+/// Location and description of a piece of input that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// Name of the input, when known
+    pub input: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based columns of the offending text, end excluded
+    pub columns: Range<usize>,
+    pub source_line: String,
+    pub expected: Option<String>,
+    pub found: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Diagnostic for the text found at the 1-based `columns` of a single line
+    pub fn new(source_line: &str, columns: Range<usize>, message: impl Into<String>) -> Self {
+        let found = source_line
+            .chars()
+            .skip(columns.start.saturating_sub(1))
+            .take(columns.len())
+            .collect();
+
+        Self {
+            input: None,
+            line: 1,
+            columns,
+            source_line: source_line.to_string(),
+            expected: None,
+            found,
+            message: message.into(),
+        }
+    }
+
+    /// Diagnostic for a missing expected token, found instead of the text at `columns`
+    pub fn expected(source_line: &str, columns: Range<usize>, expected: impl Into<String>) -> Self {
+        let mut diagnostic = Self::new(source_line, columns, "");
+        let expected = expected.into();
+        diagnostic.message = match diagnostic.found.is_empty() {
+            true => format!("expected {}, found end of line", expected),
+            false => format!("expected {}, found `{}`", expected, diagnostic.found),
+        };
+        diagnostic.expected = Some(expected);
+        diagnostic
+    }
+
+    /// Diagnostic for a nom parser failing on `source_line`. The text found is the token at the
+    /// failure position and the expectation comes from the innermost [`nom::error::context`].
+    pub fn from_nom(source_line: &str, error: &nom::Err<NomError>) -> Self {
+        let (remaining, expected, kind) = match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => (e.input, e.expected, Some(e.kind)),
+            nom::Err::Incomplete(_) => ("", None, None),
+        };
+        let start = source_line.len() - remaining.len();
+        let token_len = remaining
+            .find(char::is_whitespace)
+            .unwrap_or(remaining.len())
+            .max(remaining.chars().next().map_or(0, char::len_utf8));
+        let columns = columns(source_line, start..start + token_len);
+
+        match (expected, kind) {
+            (Some(expected), _) => Self::expected(source_line, columns, expected),
+            (None, Some(kind)) => Self::expected(source_line, columns, kind.description()),
+            (None, None) => Self::new(source_line, columns, "incomplete input"),
+        }
+    }
+
+    /// Moves the diagnostic of a fragment to its place in `source_line`, starting at byte `offset`
+    pub fn within(mut self, source_line: &str, offset: usize) -> Self {
+        let shift = source_line[..offset].chars().count();
+        self.columns = self.columns.start + shift..self.columns.end + shift;
+        self.source_line = source_line.to_string();
+        self
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    pub fn with_input(mut self, input: impl Into<String>) -> Self {
+        self.input = Some(input.into());
+        self
+    }
+
+    /// Renders the diagnostic as a source snippet with the offending text underlined
+    pub fn render(&self) -> String {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let location = match &self.input {
+            Some(input) => format!("{}:{}:{}", input, self.line, self.columns.start),
+            None => format!("{}:{}", self.line, self.columns.start),
+        };
+        let padding = " ".repeat(self.columns.start.saturating_sub(1));
+        let carets = "^".repeat(self.columns.len().max(1));
+        let label = match &self.expected {
+            Some(expected) => format!(" expected {}", expected),
+            None => String::new(),
+        };
+
+        [
+            format!("error: {}", self.message),
+            format!("{gutter}--> {location}"),
+            format!("{gutter} |"),
+            format!("{line_number} | {}", self.source_line),
+            format!("{gutter} | {padding}{carets}{label}"),
+        ]
+        .join("\n")
+    }
+}
+
+/// Converts a byte range of the line, like the ones of regex matches, to 1-based columns
+pub fn columns(source_line: &str, bytes: Range<usize>) -> Range<usize> {
+    let start = source_line[..bytes.start].chars().count() + 1;
+    start..start + source_line[bytes].chars().count()
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(input) = &self.input {
+            write!(f, "{}, ", input)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.columns.start, self.message
+        )
+    }
+}
+
+/// nom error that keeps the innermost [`nom::error::context`] as the expected input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NomError<'a> {
+    pub input: &'a str,
+    pub kind: ErrorKind,
+    pub expected: Option<&'static str>,
+}
+
+impl<'a> ParseError<&'a str> for NomError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            kind,
+            expected: None,
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> ContextError<&'a str> for NomError<'a> {
+    fn add_context(_input: &'a str, context: &'static str, mut other: Self) -> Self {
+        other.expected.get_or_insert(context);
+        other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        IResult, Parser, bytes::complete::tag, character::complete, error::context,
+        sequence::preceded,
+    };
+
+    use super::*;
+
+    fn number_parser(input: &str) -> IResult<&str, u16, NomError<'_>> {
+        preceded(
+            context("`n=`", tag("n=")),
+            context("a number", complete::u16),
+        )
+        .parse(input)
+    }
+
+    #[test]
+    fn test_new_diagnostic() {
+        let diagnostic = Diagnostic::new("2x3y0x4", 3..6, "invalid digit found in string")
+            .at_line(7)
+            .with_input("day2.txt");
+
+        assert_eq!(diagnostic.found, "3y0");
+        assert_eq!(diagnostic.expected, None);
+        assert_eq!(
+            diagnostic.to_string(),
+            "day2.txt, line 7, column 3: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_expected_diagnostic() {
+        let validations = vec![
+            ("a to b 3", 8..9, "expected `=`, found `3`"),
+            ("a to b", 7..7, "expected `=`, found end of line"),
+        ];
+
+        for (line, columns, expected_message) in validations.into_iter() {
+            let diagnostic = Diagnostic::expected(line, columns, "`=`");
+            assert_eq!(diagnostic.message, expected_message);
+            assert_eq!(diagnostic.expected, Some("`=`".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let validations = vec![
+            ("n=12", None),
+            ("x=12", Some((1..5, "`n=`", "x=12"))),
+            ("n=ab cd", Some((3..5, "a number", "ab"))),
+            ("n=", Some((3..3, "a number", ""))),
+        ];
+
+        for (input, expected_result) in validations.into_iter() {
+            match (number_parser(input), expected_result) {
+                (Ok(_), None) => (),
+                (Err(e), Some((columns, expected, found))) => {
+                    let diagnostic = Diagnostic::from_nom(input, &e);
+                    assert_eq!(diagnostic.columns, columns);
+                    assert_eq!(diagnostic.expected.as_deref(), Some(expected));
+                    assert_eq!(diagnostic.found, found);
+                }
+                (r, _) => panic!("Unexpected result {r:?} for {input}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_within() {
+        let diagnostic = Diagnostic::expected("tggle", 1..6, "`toggle`").within("tggle 1,1", 0);
+        assert_eq!(
+            (diagnostic.columns, diagnostic.found.as_str()),
+            (1..6, "tggle")
+        );
+
+        let diagnostic = Diagnostic::new("1,x", 3..4, "invalid digit").within("turn on 1,x", 8);
+        assert_eq!(diagnostic.columns, 11..12);
+        assert_eq!(diagnostic.source_line, "turn on 1,x");
+        assert_eq!(diagnostic.found, "x");
+    }
+
+    #[test]
+    fn test_columns() {
+        assert_eq!(columns("turn on 1,2", 5..7), 6..8);
+        assert_eq!(columns("é to b", 3..5), 3..5);
+        assert_eq!(columns("a", 1..1), 2..2);
+    }
+
+    #[test]
+    fn test_render() {
+        let diagnostic = Diagnostic::expected("London to Belfast 518", 19..22, "`=`")
+            .at_line(12)
+            .with_input("day9.txt");
+        let expected = [
+            "error: expected `=`, found `518`",
+            "  --> day9.txt:12:19",
+            "   |",
+            "12 | London to Belfast 518",
+            "   |                   ^^^ expected `=`",
+        ];
+        assert_eq!(diagnostic.render(), expected.join("\n"));
+
+        let diagnostic = Diagnostic::new("toggle", 7..7, "incomplete command");
+        assert!(diagnostic.render().ends_with("1 | toggle\n  |       ^"));
+    }
+
+    #[test]
+    fn test_serialize() {
+        let diagnostic = Diagnostic::expected("a to b 3", 8..9, "`=`").with_input("day9.txt");
+        let value = serde_json::to_value(&diagnostic).unwrap();
+
+        assert_eq!(value["input"], "day9.txt");
+        assert_eq!(value["line"], 1);
+        assert_eq!(value["columns"], serde_json::json!({"start": 8, "end": 9}));
+        assert_eq!(value["expected"], "`=`");
+        assert_eq!(value["found"], "3");
+    }
+}
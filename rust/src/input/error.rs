@@ -1,9 +1,73 @@
+use std::{ops::Range, path::PathBuf};
+
+use super::diagnostic::Diagnostic;
+
 pub type Result<T> = core::result::Result<T, InputError>;
 
 #[derive(thiserror::Error, Debug)]
 #[error("Problem Input Error")]
 pub enum InputError {
+    #[error(transparent)]
     IOError(#[from] std::io::Error),
-    #[error("Error parsing input: {0}")]
-    Parsing(String),
+    /// `source` is the error of the underlying parser, when there is one
+    #[error("Error parsing input at {diagnostic}")]
+    Parsing {
+        diagnostic: Box<Diagnostic>,
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    #[error("No inputs of day {day} found in {}", path.display())]
+    NoInputs { path: PathBuf, day: u8 },
+}
+
+impl InputError {
+    /// Parsing error of the text at the 1-based `columns` of a single line
+    pub fn parsing(
+        source_line: &str,
+        columns: Range<usize>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        let source = source.into();
+        Self::Parsing {
+            diagnostic: Box::new(Diagnostic::new(source_line, columns, source.to_string())),
+            source: Some(source),
+        }
+    }
+
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Self::Parsing { diagnostic, .. } => Some(diagnostic),
+            _ => None,
+        }
+    }
+
+    /// Moves a parsing error of a fragment to its place in `source_line`, starting at byte `offset`
+    pub fn within(self, source_line: &str, offset: usize) -> Self {
+        match self {
+            Self::Parsing { diagnostic, source } => Self::Parsing {
+                diagnostic: Box::new(diagnostic.within(source_line, offset)),
+                source,
+            },
+            e => e,
+        }
+    }
+
+    /// Moves a parsing error of a single line to the given line of the whole input
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            Self::Parsing { diagnostic, source } => Self::Parsing {
+                diagnostic: Box::new(diagnostic.at_line(line)),
+                source,
+            },
+            e => e,
+        }
+    }
+}
+
+impl From<Diagnostic> for InputError {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self::Parsing {
+            diagnostic: Box::new(diagnostic),
+            source: None,
+        }
+    }
 }
@@ -1,33 +1,379 @@
+pub mod diagnostic;
 pub mod error;
 
-use error::Result;
-use std::{fs, path::Path, str::FromStr};
+use error::{InputError, Result};
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-const INPUT_BASE_PATH: &str = "../inputs/";
+/// Inputs directory of this repository, resolved at build time
+const INPUT_BASE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs/");
+/// Environment variable overriding [`INPUT_BASE_PATH`]
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+/// Name of the `day{day}.txt` input of a day
+pub const DEFAULT_INPUT_NAME: &str = "default";
 
-pub fn parse_input_lines<T: FromStr>(input: &str) -> Result<Vec<T>> {
-    Ok(input
+/// Where the input of a problem is read from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `{year}/day{day}.txt` inside the inputs directory given by [`get_input_base_path`]
+    #[default]
+    Default,
+    /// `{year}/day{day}.txt` inside the given inputs directory
+    Dir(PathBuf),
+    /// Named input of [`get_named_input_path`] inside the given inputs directory
+    Named(PathBuf, String),
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    pub fn read(&self, year: u16, day: u8) -> Result<String> {
+        let content = match self {
+            Self::Default | Self::Dir(_) | Self::Named(..) | Self::File(_) => {
+                fs::read_to_string(self.path(year, day).unwrap())?
+            }
+            Self::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content)?;
+                content
+            }
+            Self::Inline(content) => content.clone(),
+        };
+        Ok(content)
+    }
+
+    /// Path of the input file, if the input is read from one
+    pub fn path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        match self {
+            Self::Default => Some(get_default_input_path(year, day)),
+            Self::Dir(dir) => Some(get_input_path(dir, year, day)),
+            Self::Named(dir, name) => Some(get_named_input_path(dir, year, day, name)),
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin | Self::Inline(_) => None,
+        }
+    }
+
+    /// Human readable name of the input
+    pub fn name(&self, year: u16, day: u8) -> String {
+        match self {
+            Self::Stdin => "<stdin>".to_string(),
+            Self::Inline(_) => "<inline>".to_string(),
+            _ => self.path(year, day).unwrap().display().to_string(),
+        }
+    }
+
+    /// Name of the input in the inputs directory, as used by the answers files
+    pub fn input_name(&self) -> Option<&str> {
+        match self {
+            Self::Default | Self::Dir(_) => Some(DEFAULT_INPUT_NAME),
+            Self::Named(_, name) => Some(name),
+            Self::File(_) | Self::Stdin | Self::Inline(_) => None,
+        }
+    }
+}
+
+impl From<&str> for InputSource {
+    /// Builds a file source from a path, where `-` stands for stdin
+    fn from(path: &str) -> Self {
+        match path {
+            "-" => Self::Stdin,
+            path => Self::File(path.into()),
+        }
+    }
+}
+
+/// Parses every line of the input, failing with [`InputError::Parsing`] on the first line that
+/// does not parse
+pub fn parse_input_lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    parse_input_lines_with(input, |line| {
+        line.parse::<T>()
+            .map_err(|e| InputError::parsing(line, 1..line.chars().count() + 1, e))
+    })
+}
+
+/// Parses every line of the input with `parse_line`, which reports errors of a single line
+pub fn parse_input_lines_with<T>(
+    input: &str,
+    mut parse_line: impl FnMut(&str) -> Result<T>,
+) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_line(line).map_err(|e| e.at_line(idx + 1)))
+        .collect()
+}
+
+/// Parses the lines of the input, silently skipping the ones that do not parse
+pub fn parse_input_lines_lenient<T: FromStr>(input: &str) -> Vec<T> {
+    input
         .lines()
         .filter_map(|line| line.parse::<T>().ok())
-        .collect())
+        .collect()
+}
+
+/// Parses every `sep` separated field of the line, failing with [`InputError::Parsing`] on the
+/// first field that does not parse
+pub fn split_line<T>(line: &str, sep: char) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let mut column = 1;
+    line.split(sep)
+        .map(|field| {
+            let columns = column..column + field.chars().count();
+            column = columns.end + 1;
+            field
+                .parse()
+                .map_err(|e| InputError::parsing(line, columns, e))
+        })
+        .collect()
 }
 
-pub fn split_line<T: FromStr>(line: &str, sep: char) -> Result<Vec<T>> {
-    Ok(line
-        .split(sep)
-        .filter_map(|c| c.parse().ok())
-        .collect::<Vec<T>>())
+/// Parses the `sep` separated fields of the line, silently skipping the ones that do not parse
+pub fn split_line_lenient<T: FromStr>(line: &str, sep: char) -> Vec<T> {
+    line.split(sep).filter_map(|c| c.parse().ok()).collect()
 }
 
-#[allow(dead_code)]
-pub fn parse_file_lines<T: FromStr>(file_path: impl AsRef<Path>) -> Result<Vec<T>> {
+pub fn parse_file_lines<T>(file_path: impl AsRef<Path>) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
     let file_content = fs::read_to_string(&file_path)?;
 
     parse_input_lines(&file_content)
 }
 
-pub fn get_default_input_path(year: u16, day: u8) -> String {
-    let mut input_path = INPUT_BASE_PATH.to_string();
-    input_path.push_str(&format!("{}/day{}.txt", year, day));
-    input_path
+/// Base directory of the problem inputs, taken from [`INPUT_DIR_ENV`] when it is set
+pub fn get_input_base_path() -> PathBuf {
+    match env::var_os(INPUT_DIR_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => fs::canonicalize(INPUT_BASE_PATH).unwrap_or_else(|_| INPUT_BASE_PATH.into()),
+    }
+}
+
+pub fn get_input_path(base_path: impl AsRef<Path>, year: u16, day: u8) -> PathBuf {
+    get_named_input_path(base_path, year, day, DEFAULT_INPUT_NAME)
+}
+
+/// Path of the `day{day}_{name}.txt` input, or `day{day}.txt` for [`DEFAULT_INPUT_NAME`]
+pub fn get_named_input_path(
+    base_path: impl AsRef<Path>,
+    year: u16,
+    day: u8,
+    name: &str,
+) -> PathBuf {
+    let file_name = match name {
+        DEFAULT_INPUT_NAME => format!("day{}.txt", day),
+        name => format!("day{}_{}.txt", day, name),
+    };
+    base_path.as_ref().join(year.to_string()).join(file_name)
+}
+
+pub fn get_default_input_path(year: u16, day: u8) -> PathBuf {
+    get_input_path(get_input_base_path(), year, day)
+}
+
+/// Names of the inputs of a day found in the inputs directory, [`DEFAULT_INPUT_NAME`] first and
+/// the `day{day}_{name}.txt` ones sorted by name
+pub fn get_input_names(base_path: impl AsRef<Path>, year: u16, day: u8) -> Result<Vec<String>> {
+    let dir = base_path.as_ref().join(year.to_string());
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let prefix = format!("day{}", day);
+    let mut names = Vec::new();
+    for entry in entries {
+        let file_name = entry?.file_name();
+        let name = file_name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|name| name.strip_suffix(".txt"));
+        match name {
+            Some("") => names.push(DEFAULT_INPUT_NAME.to_string()),
+            Some(name) => {
+                if let Some(name) = name.strip_prefix('_').filter(|n| !n.is_empty()) {
+                    names.push(name.to_string());
+                }
+            }
+            None => (),
+        }
+    }
+    names.sort_by_key(|name| (name != DEFAULT_INPUT_NAME, name.clone()));
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input_lines() {
+        let lines: Vec<usize> = parse_input_lines("1\n22\n333").unwrap();
+        assert_eq!(lines, vec![1, 22, 333]);
+
+        let lines: Vec<String> = parse_input_lines("a\n\nb").unwrap();
+        assert_eq!(lines, vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn test_parse_input_lines_reports_malformed_line() {
+        match parse_input_lines::<usize>("1\n22\n3x3\n4") {
+            Err(InputError::Parsing { diagnostic, source }) => {
+                assert_eq!((diagnostic.line, diagnostic.columns), (3, 1..4));
+                assert_eq!(diagnostic.found, "3x3");
+                assert_eq!(diagnostic.message, "invalid digit found in string");
+                assert!(source.is_some());
+            }
+            r => panic!("Should have been a parsing error, got {r:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_input_lines_lenient() {
+        let lines: Vec<usize> = parse_input_lines_lenient("1\n22\n3x3\n4");
+        assert_eq!(lines, vec![1, 22, 4]);
+    }
+
+    #[test]
+    fn test_split_line() {
+        let fields: Vec<usize> = split_line("2x3x4", 'x').unwrap();
+        assert_eq!(fields, vec![2, 3, 4]);
+
+        let validations = vec![
+            ("2xx4", 3..3, ""),
+            ("20x3y0x4", 4..7, "3y0"),
+            ("a", 1..2, "a"),
+        ];
+        for (input, expected_columns, expected_text) in validations.into_iter() {
+            match split_line::<usize>(input, 'x') {
+                Err(InputError::Parsing { diagnostic, .. }) => {
+                    assert_eq!(diagnostic.line, 1);
+                    assert_eq!(diagnostic.columns, expected_columns);
+                    assert_eq!(diagnostic.found, expected_text);
+                    assert_eq!(diagnostic.source_line, input);
+                }
+                r => panic!("Should have been a parsing error, got {r:?}"),
+            }
+        }
+
+        let fields: Vec<usize> = split_line_lenient("20x3y0x4", 'x');
+        assert_eq!(fields, vec![20, 4]);
+    }
+
+    #[test]
+    fn test_parse_input_lines_with() {
+        let result = parse_input_lines_with("2x3\n1x1x", |line| split_line::<u8>(line, 'x'));
+        match result {
+            Err(e) => {
+                let diagnostic = e.diagnostic().unwrap();
+                assert_eq!((diagnostic.line, diagnostic.columns.start), (2, 5));
+            }
+            r => panic!("Should have been a parsing error, got {r:?}"),
+        }
+    }
+
+    #[test]
+    fn test_input_source_from_path() {
+        assert_eq!(InputSource::from("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from("../inputs/2015/day8_jkpr.txt"),
+            InputSource::File("../inputs/2015/day8_jkpr.txt".into())
+        );
+    }
+
+    #[test]
+    fn test_input_source_paths() {
+        let validations = vec![
+            (
+                InputSource::Dir("../inputs".into()),
+                Some(PathBuf::from("../inputs/2015/day9.txt")),
+            ),
+            (
+                InputSource::Named("../inputs".into(), "jkpr".into()),
+                Some(PathBuf::from("../inputs/2015/day9_jkpr.txt")),
+            ),
+            (
+                InputSource::File("../inputs/2015/day9_jkpr.txt".into()),
+                Some(PathBuf::from("../inputs/2015/day9_jkpr.txt")),
+            ),
+            (InputSource::Stdin, None),
+            (InputSource::Inline("".into()), None),
+        ];
+
+        for (source, expected_result) in validations.into_iter() {
+            assert_eq!(source.path(2015, 9), expected_result);
+        }
+    }
+
+    #[test]
+    fn test_named_input_path() {
+        let validations = vec![
+            (DEFAULT_INPUT_NAME, "../inputs/2015/day8.txt"),
+            ("jkpr", "../inputs/2015/day8_jkpr.txt"),
+        ];
+
+        for (name, expected_result) in validations.into_iter() {
+            let path = get_named_input_path("../inputs", 2015, 8, name);
+            assert_eq!(path, PathBuf::from(expected_result));
+        }
+    }
+
+    #[test]
+    fn test_input_names() {
+        let validations = vec![
+            (8, vec!["default", "jkpr", "jocelyn_stericher"]),
+            (9, vec!["default", "jkpr"]),
+            (1, vec!["default"]),
+            (24, vec![]),
+        ];
+
+        for (day, expected_result) in validations.into_iter() {
+            assert_eq!(
+                get_input_names("../inputs", 2015, day).unwrap(),
+                expected_result
+            );
+        }
+        assert!(get_input_names("../missing", 2015, 8).unwrap().is_empty());
+
+        let names = vec![
+            (InputSource::Default, Some(DEFAULT_INPUT_NAME)),
+            (
+                InputSource::Named("../inputs".into(), "jkpr".into()),
+                Some("jkpr"),
+            ),
+            (InputSource::Stdin, None),
+        ];
+        for (source, expected_result) in names.into_iter() {
+            assert_eq!(source.input_name(), expected_result);
+        }
+    }
+
+    #[test]
+    fn test_read_input_source() {
+        let inline = InputSource::Inline("London to Dublin = 464".into());
+        assert_eq!(inline.read(2015, 9).unwrap(), "London to Dublin = 464");
+
+        let from_dir = InputSource::Dir("../inputs".into()).read(2015, 9).unwrap();
+        let from_file = InputSource::File("../inputs/2015/day9.txt".into())
+            .read(2015, 9)
+            .unwrap();
+        assert_eq!(from_dir, from_file);
+
+        let missing = InputSource::Dir("../missing".into()).read(2015, 9);
+        assert!(missing.is_err());
+    }
 }
@@ -0,0 +1,37 @@
+//! Advent of Code solutions along with everything needed to run and check them.
+//!
+//! Solutions are registered by year and day, and a [`problem::Problem`] pairs a registered
+//! [`solution::Solution`] with its inputs:
+//!
+//! ```
+//! use aoc::{problem::Problem, solution::{Part, answer::Answer}};
+//!
+//! let problem = Problem::new(2015, 1).unwrap();
+//! let answer = problem.get_solution().solve(Part::One, "(()(()(").unwrap();
+//! assert_eq!(answer, Answer::Integer(3));
+//! ```
+//!
+//! The `AoC` binary is a thin wrapper around [`cli::main`].
+
+extern crate nalgebra as na;
+
+#[macro_use]
+extern crate log;
+
+pub mod answers;
+mod bench;
+pub mod cli;
+mod client;
+pub mod error;
+mod examples;
+pub mod graph;
+pub mod input;
+mod output;
+pub mod pow;
+pub mod problem;
+mod runner;
+mod scaffold;
+pub mod solution;
+mod submit;
+mod verify;
+mod watch;
@@ -0,0 +1,524 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    problem::Problem,
+    runner::PartRecord,
+    solution::{Part, answer::Answer},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable text
+    #[default]
+    Text,
+    /// A single JSON array holding every record
+    Json,
+    /// One JSON record per line
+    Ndjson,
+    Csv,
+}
+
+/// A result that can be written in every [`OutputFormat`]
+pub trait Report: Serialize {
+    fn csv_header() -> &'static [&'static str];
+
+    fn csv_fields(&self) -> Vec<String>;
+
+    fn write_text(&self, writer: &mut impl Write) -> io::Result<()>;
+}
+
+/// Writes run results in the selected [`OutputFormat`]
+pub struct Reporter<W: Write, R: Report = PartRecord> {
+    format: OutputFormat,
+    writer: W,
+    records: Vec<R>,
+}
+
+impl<W: Write, R: Report> Reporter<W, R> {
+    pub fn new(format: OutputFormat, mut writer: W) -> io::Result<Self> {
+        if format == OutputFormat::Csv {
+            writeln!(writer, "{}", R::csv_header().join(","))?;
+        }
+
+        Ok(Self {
+            format,
+            writer,
+            records: Vec::new(),
+        })
+    }
+
+    pub fn start_problem(&mut self, problem: &Problem) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Text => writeln!(
+                self.writer,
+                "---- {} Day {}: {} ----",
+                problem.get_year(),
+                problem.get_day(),
+                problem.get_title()
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts the records of one of the inputs of the current problem
+    pub fn start_input(&mut self, input_name: &str) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Text => writeln!(self.writer, "-- {} --", input_name),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reports an error that is not tied to any record. Machine readable formats send it to stderr.
+    pub fn report_error(&mut self, message: &str) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Text => writeln!(self.writer, "Error: {}", message),
+            _ => {
+                eprintln!("Error: {}", message);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn report(&mut self, record: R) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Text => record.write_text(&mut self.writer)?,
+            OutputFormat::Ndjson => {
+                serde_json::to_writer(&mut self.writer, &record)?;
+                writeln!(self.writer)?;
+            }
+            OutputFormat::Csv => {
+                let fields: Vec<String> = record
+                    .csv_fields()
+                    .iter()
+                    .map(|f| escape_csv_field(f))
+                    .collect();
+                writeln!(self.writer, "{}", fields.join(","))?;
+            }
+            OutputFormat::Json => (),
+        }
+        self.records.push(record);
+        Ok(())
+    }
+
+    /// Flushes the pending output and returns every reported record
+    pub fn finish(mut self) -> io::Result<Vec<R>> {
+        if self.format == OutputFormat::Json {
+            serde_json::to_writer_pretty(&mut self.writer, &self.records)?;
+            writeln!(self.writer)?;
+        }
+        self.writer.flush()?;
+        Ok(self.records)
+    }
+}
+
+pub fn write_solution(writer: &mut impl Write, title: &str, lines: &[String]) -> io::Result<()> {
+    let title = title.trim();
+    let indent_size = title.len() + 1;
+
+    writeln!(writer, "{} {}", title, lines.first().map_or("", |l| l))?;
+    for line in lines.iter().skip(1) {
+        writeln!(writer, "{}{}", " ".repeat(indent_size), &line)?;
+    }
+    Ok(())
+}
+
+/// Writes a table with the answer, time and status of every part
+pub fn write_summary(writer: &mut impl Write, records: &[PartRecord]) -> io::Result<()> {
+    let header = ["Day", "Part", "Answer", "Time", "Status"].map(String::from);
+    let rows: Vec<Vec<String>> = records
+        .iter()
+        .map(|record| {
+            vec![
+                format!("{} {}", record.year, record.day),
+                record.part.to_string(),
+                short_answer(record.answer.as_ref()),
+                format!("{:.2?}", Duration::from_nanos(record.elapsed_ns)),
+                record.status().to_string(),
+            ]
+        })
+        .collect();
+
+    writeln!(writer, "---- Summary ----")?;
+    write_table(writer, &header, &rows)
+}
+
+/// Writes a table with the answers of every part side by side for each input of a day, along
+/// with how they compare to the known answers
+pub fn write_comparison(
+    writer: &mut impl Write,
+    records: &[PartRecord],
+    parts: &[Part],
+) -> io::Result<()> {
+    let header: Vec<String> = ["Day".to_string(), "Input".to_string()]
+        .into_iter()
+        .chain(parts.iter().map(|part| format!("Part {}", part)))
+        .collect();
+
+    // Records of the same day and input are reported next to each other
+    let rows: Vec<Vec<String>> = records
+        .chunk_by(|a, b| (a.year, a.day, &a.input) == (b.year, b.day, &b.input))
+        .map(|records| {
+            let input = Path::new(&records[0].input)
+                .file_name()
+                .map_or(records[0].input.clone(), |name| {
+                    name.to_string_lossy().to_string()
+                });
+            let cells = parts.iter().map(|&part| {
+                records
+                    .iter()
+                    .find(|record| record.part == part)
+                    .map_or("-".to_string(), comparison_cell)
+            });
+            [format!("{} {}", records[0].year, records[0].day), input]
+                .into_iter()
+                .chain(cells)
+                .collect()
+        })
+        .collect();
+
+    writeln!(writer, "---- Comparison ----")?;
+    write_table(writer, &header, &rows)
+}
+
+fn comparison_cell(record: &PartRecord) -> String {
+    if record.error.is_some() {
+        return record.status().to_string();
+    }
+    let answer = short_answer(record.answer.as_ref());
+    match &record.expected {
+        Some(expected) if record.is_wrong() => {
+            format!("{} (expected {})", answer, short_answer(Some(expected)))
+        }
+        Some(_) => format!("{} (ok)", answer),
+        None => answer,
+    }
+}
+
+/// First line of an answer, marking the answers that have more
+fn short_answer(answer: Option<&Answer>) -> String {
+    match answer.map(Answer::lines).as_deref() {
+        None | Some([]) => "-".to_string(),
+        Some([line]) => line.clone(),
+        Some([line, ..]) => format!("{}…", line),
+    }
+}
+
+fn write_table(writer: &mut impl Write, header: &[String], rows: &[Vec<String>]) -> io::Result<()> {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows.iter() {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.chars().count());
+        }
+    }
+
+    for row in std::iter::once(header).chain(rows.iter().map(|row| row.as_slice())) {
+        let fields: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(field, &width)| format!("{:<width$}", field))
+            .collect();
+        writeln!(writer, "{}", fields.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+impl Report for PartRecord {
+    fn csv_header() -> &'static [&'static str] {
+        &[
+            "year",
+            "day",
+            "part",
+            "answer",
+            "error",
+            "parse_ns",
+            "elapsed_ns",
+            "input",
+        ]
+    }
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.answer
+                .as_ref()
+                .map(Answer::to_string)
+                .unwrap_or_default(),
+            self.error.clone().unwrap_or_default(),
+            self.parse_ns.to_string(),
+            self.elapsed_ns.to_string(),
+            self.input.clone(),
+        ]
+    }
+
+    fn write_text(&self, writer: &mut impl Write) -> io::Result<()> {
+        let title = format!("Part {}:", self.part);
+        match (&self.error, &self.diagnostic) {
+            (Some(error), _) if self.timed_out => {
+                writeln!(writer, "{} TIMEOUT: {}\n", title, error)
+            }
+            (Some(_), Some(diagnostic)) => {
+                writeln!(writer, "{} Error parsing input", title)?;
+                writeln!(writer, "{}\n", diagnostic.render())
+            }
+            (Some(error), None) => writeln!(writer, "{} Error: {}\n", title, error),
+            (None, _) => {
+                let answer = self.answer.as_ref().map(Answer::lines).unwrap_or_default();
+                write_solution(writer, &title, &answer)?;
+                if let Some(expected) = self.expected.as_ref().filter(|_| self.is_wrong()) {
+                    write_solution(writer, "Expected:", &expected.lines())?;
+                }
+                writeln!(
+                    writer,
+                    "Elapsed time: {:?} (parsing: {:?})\n",
+                    Duration::from_nanos(self.elapsed_ns),
+                    Duration::from_nanos(self.parse_ns)
+                )
+            }
+        }
+    }
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::diagnostic::Diagnostic;
+
+    use super::*;
+
+    fn records() -> Vec<PartRecord> {
+        vec![
+            PartRecord {
+                year: 2015,
+                day: 3,
+                part: Part::One,
+                answer: Some(Answer::Lines(vec!["2".into(), "4".into()])),
+                error: None,
+                diagnostic: None,
+                parse_ns: 100,
+                elapsed_ns: 1500,
+                timed_out: false,
+                input: "day3.txt".into(),
+                expected: None,
+            },
+            PartRecord {
+                year: 2015,
+                day: 3,
+                part: Part::Two,
+                answer: None,
+                error: Some("wrong \"instructions\", line 1".into()),
+                diagnostic: None,
+                parse_ns: 0,
+                elapsed_ns: 20,
+                timed_out: false,
+                input: "day3.txt".into(),
+                expected: None,
+            },
+        ]
+    }
+
+    fn render(format: OutputFormat) -> String {
+        let mut buffer = Vec::new();
+        let mut reporter: Reporter<_> = Reporter::new(format, &mut buffer).unwrap();
+        for record in records() {
+            reporter.report(record).unwrap();
+        }
+        assert_eq!(reporter.finish().unwrap(), records());
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_escape_csv_field() {
+        let validations = vec![
+            ("1342", "1342"),
+            ("", ""),
+            ("a,b", "\"a,b\""),
+            ("say \"hi\"", "\"say \"\"hi\"\"\""),
+            ("2\n4", "\"2\n4\""),
+        ];
+
+        for (input, expected_result) in validations.into_iter() {
+            assert_eq!(escape_csv_field(input), expected_result);
+        }
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let expected = [
+            "year,day,part,answer,error,parse_ns,elapsed_ns,input",
+            "2015,3,1,\"2\n4\",,100,1500,day3.txt",
+            "2015,3,2,,\"wrong \"\"instructions\"\", line 1\",0,20,day3.txt",
+            "",
+        ];
+        assert_eq!(render(OutputFormat::Csv), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_ndjson_output() {
+        let output = render(OutputFormat::Ndjson);
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["part"], 1);
+        assert_eq!(lines[0]["answer"], serde_json::json!(["2", "4"]));
+        assert_eq!(lines[0]["error"], serde_json::Value::Null);
+        assert_eq!(lines[1]["elapsed_ns"], 20);
+        assert_eq!(lines[1]["input"], "day3.txt");
+    }
+
+    #[test]
+    fn test_json_output() {
+        let output: serde_json::Value = serde_json::from_str(&render(OutputFormat::Json)).unwrap();
+
+        assert_eq!(output.as_array().unwrap().len(), 2);
+        assert_eq!(output[0]["year"], 2015);
+        assert_eq!(output[1]["error"], "wrong \"instructions\", line 1");
+    }
+
+    #[test]
+    fn test_text_output() {
+        let expected = [
+            "Part 1: 2",
+            "        4",
+            "Elapsed time: 1.5µs (parsing: 100ns)",
+            "",
+            "Part 2: Error: wrong \"instructions\", line 1",
+            "",
+            "",
+        ];
+        assert_eq!(render(OutputFormat::Text), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_summary_output() {
+        let mut records = records();
+        records[0].answer = Some(Answer::Integer(2));
+        records.push(PartRecord {
+            day: 12,
+            answer: Some(Answer::Lines(vec!["[1,2]".into(), "3".into()])),
+            error: None,
+            elapsed_ns: 12_345_678,
+            ..records[1].clone()
+        });
+        records.push(PartRecord {
+            day: 4,
+            error: Some("Exceeded the timeout of 1s".into()),
+            elapsed_ns: 1_000_200_000,
+            timed_out: true,
+            ..records[1].clone()
+        });
+
+        let mut buffer = Vec::new();
+        write_summary(&mut buffer, &records).unwrap();
+        let expected = [
+            "---- Summary ----",
+            "Day      Part  Answer  Time     Status",
+            "2015 3   1     2       1.50µs   OK",
+            "2015 3   2     -       20.00ns  ERROR",
+            "2015 12  2     [1,2]…  12.35ms  OK",
+            "2015 4   2     -       1.00s    TIMEOUT",
+            "",
+        ];
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected.join("\n"));
+
+        let mut buffer = Vec::new();
+        records[3].write_text(&mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "Part 2: TIMEOUT: Exceeded the timeout of 1s\n\n"
+        );
+    }
+
+    #[test]
+    fn test_comparison_output() {
+        let mut records = records();
+        records[0].expected = records[0].answer.clone();
+        records[1].input = "../inputs/2015/day3.txt".into();
+        records[0].input = records[1].input.clone();
+        let named = PartRecord {
+            answer: Some(Answer::Integer(7)),
+            error: None,
+            expected: Some(Answer::Integer(8)),
+            input: "../inputs/2015/day3_jkpr.txt".into(),
+            ..records[1].clone()
+        };
+        records.push(PartRecord {
+            part: Part::One,
+            expected: None,
+            ..named.clone()
+        });
+        records.push(named);
+
+        let mut buffer = Vec::new();
+        write_comparison(&mut buffer, &records, &[Part::One, Part::Two]).unwrap();
+        let expected = [
+            "---- Comparison ----",
+            "Day     Input          Part 1   Part 2",
+            "2015 3  day3.txt       2… (ok)  ERROR",
+            "2015 3  day3_jkpr.txt  7        7 (expected 8)",
+            "",
+        ];
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected.join("\n"));
+
+        let mut buffer = Vec::new();
+        records[3].write_text(&mut buffer).unwrap();
+        assert!(
+            String::from_utf8(buffer)
+                .unwrap()
+                .starts_with("Part 2: 7\nExpected: 8\n")
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_output() {
+        let diagnostic = Diagnostic::expected("London to Belfast 518", 19..22, "`=`")
+            .at_line(2)
+            .with_input("day9.txt");
+        let record = PartRecord {
+            error: Some(format!("Error parsing input at {}", diagnostic)),
+            diagnostic: Some(diagnostic),
+            ..PartRecord::failed(
+                &Problem::new(2015, 9).unwrap(),
+                Part::One,
+                "day9.txt",
+                "".into(),
+            )
+        };
+
+        let mut buffer = Vec::new();
+        record.write_text(&mut buffer).unwrap();
+        let expected = [
+            "Part 1: Error parsing input",
+            "error: expected `=`, found `518`",
+            " --> day9.txt:2:19",
+            "  |",
+            "2 | London to Belfast 518",
+            "  |                   ^^^ expected `=`",
+            "",
+            "",
+        ];
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected.join("\n"));
+
+        let value = serde_json::to_value(&record).unwrap();
+        assert_eq!(value["diagnostic"]["line"], 2);
+        assert_eq!(value["diagnostic"]["found"], "518");
+    }
+}
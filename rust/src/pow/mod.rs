@@ -0,0 +1,748 @@
+use std::{
+    collections::BTreeSet,
+    env,
+    fmt::Display,
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::Digest as _;
+
+use crate::solution::{cancel, error::Result};
+
+/// Environment variable overriding the directory of the mining checkpoints, which defaults to
+/// `aoc-checkpoints` inside the temporary directory
+pub const CHECKPOINT_DIR_ENV: &str = "AOC_CHECKPOINT_DIR";
+/// Nonces handed out to a worker at a time
+const BLOCK_SIZE: u64 = 1 << 14;
+/// Blocks searched between two checkpoints
+const CHECKPOINT_BLOCKS: u64 = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Md5 => "md5",
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Hash state of an [`Algorithm`], cloned to reuse the state of a shared prefix
+#[derive(Clone)]
+enum Hasher {
+    Md5(md5::Context),
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+}
+
+impl Hasher {
+    fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Md5 => Self::Md5(md5::Context::new()),
+            Algorithm::Sha1 => Self::Sha1(sha1::Sha1::new()),
+            Algorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Md5(context) => context.consume(data),
+            Self::Sha1(sha1) => sha1.update(data),
+            Self::Sha256(sha256) => sha256.update(data),
+        }
+    }
+
+    fn finalize(self) -> HashDigest {
+        match self {
+            Self::Md5(context) => HashDigest::new(&context.compute().0),
+            Self::Sha1(sha1) => HashDigest::new(&sha1.finalize()),
+            Self::Sha256(sha256) => HashDigest::new(&sha256.finalize()),
+        }
+    }
+}
+
+/// Digest of any [`Algorithm`], without allocating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashDigest {
+    bytes: [u8; 32],
+    len: usize,
+}
+
+impl HashDigest {
+    fn new(digest: &[u8]) -> Self {
+        let mut bytes = [0; 32];
+        bytes[..digest.len()].copy_from_slice(digest);
+        Self {
+            bytes,
+            len: digest.len(),
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Lowercase hex digits of the digest, written to `buffer`
+    pub fn write_hex<'a>(&self, buffer: &'a mut [u8; 64]) -> &'a [u8] {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        for (i, byte) in self.as_bytes().iter().enumerate() {
+            buffer[2 * i] = DIGITS[(byte >> 4) as usize];
+            buffer[2 * i + 1] = DIGITS[(byte & 0xf) as usize];
+        }
+        &buffer[..2 * self.len]
+    }
+}
+
+impl Display for HashDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut buffer = [0; 64];
+        // Hex digits are always valid UTF-8
+        write!(
+            f,
+            "{}",
+            std::str::from_utf8(self.write_hex(&mut buffer)).unwrap()
+        )
+    }
+}
+
+/// Condition a digest has to meet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Predicate {
+    LeadingZeroBits(u32),
+    /// Leading zeroes of the hex digest
+    LeadingZeroNibbles(u32),
+    /// The hex digest contains the given lowercase string
+    Contains(String),
+    /// The hex digest holds the same digit this many times in a row
+    RepeatedRun(usize),
+}
+
+impl Predicate {
+    pub fn matches(&self, digest: &HashDigest) -> bool {
+        match self {
+            Self::LeadingZeroBits(bits) => has_leading_zero_bits(digest.as_bytes(), *bits),
+            Self::LeadingZeroNibbles(nibbles) => {
+                has_leading_zero_bits(digest.as_bytes(), nibbles * 4)
+            }
+            Self::Contains(needle) => {
+                let mut buffer = [0; 64];
+                let hex = digest.write_hex(&mut buffer);
+                needle.is_empty()
+                    || hex
+                        .windows(needle.len())
+                        .any(|window| window == needle.as_bytes())
+            }
+            Self::RepeatedRun(len) => {
+                let mut buffer = [0; 64];
+                let hex = digest.write_hex(&mut buffer);
+                hex.windows((*len).max(1))
+                    .any(|window| window.iter().all(|&d| d == window[0]))
+            }
+        }
+    }
+}
+
+fn has_leading_zero_bits(digest: &[u8], bits: u32) -> bool {
+    let (bytes, rest) = ((bits / 8) as usize, bits % 8);
+    match digest.get(..bytes + (rest > 0) as usize) {
+        Some(head) => {
+            head[..bytes].iter().all(|&b| b == 0) && (rest == 0 || head[bytes] >> (8 - rest) == 0)
+        }
+        None => false,
+    }
+}
+
+/// Search of the nonces whose digest of `{prefix}{nonce}` meets a [`Predicate`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Search {
+    pub prefix: String,
+    pub algorithm: Algorithm,
+    pub predicate: Predicate,
+    /// Number of times the hex digest is hashed again, as in key stretching
+    pub stretch: usize,
+}
+
+impl Search {
+    pub fn new(prefix: &str, algorithm: Algorithm, predicate: Predicate) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            algorithm,
+            predicate,
+            stretch: 0,
+        }
+    }
+
+    pub fn with_stretch(self, stretch: usize) -> Self {
+        Self { stretch, ..self }
+    }
+
+    /// Digest of the nonce, stretched
+    pub fn digest(&self, nonce: u64) -> HashDigest {
+        let mut hasher = Hasher::new(self.algorithm);
+        hasher.update(self.prefix.as_bytes());
+        self.digest_from(&hasher, nonce, &mut [0; 20])
+    }
+
+    pub fn matches(&self, nonce: u64) -> bool {
+        self.predicate.matches(&self.digest(nonce))
+    }
+
+    /// Digest of the nonce from the state of the hasher after the prefix
+    fn digest_from(&self, prefixed: &Hasher, nonce: u64, buffer: &mut [u8; 20]) -> HashDigest {
+        let mut hasher = prefixed.clone();
+        hasher.update(write_decimal(nonce, buffer));
+        let mut digest = hasher.finalize();
+
+        let mut hex = [0; 64];
+        for _ in 0..self.stretch {
+            let mut hasher = Hasher::new(self.algorithm);
+            hasher.update(digest.write_hex(&mut hex));
+            digest = hasher.finalize();
+        }
+        digest
+    }
+
+    /// Nonces of the range that match, in order
+    fn search_block(&self, prefixed: &Hasher, nonces: Range<u64>) -> Vec<u64> {
+        let mut buffer = [0; 20];
+        nonces
+            .filter(|&nonce| {
+                self.predicate
+                    .matches(&self.digest_from(prefixed, nonce, &mut buffer))
+            })
+            .collect()
+    }
+}
+
+/// Writes the decimal digits of `n` at the end of `buffer`, which is long enough for any `u64`
+fn write_decimal(mut n: u64, buffer: &mut [u8; 20]) -> &[u8] {
+    let mut start = buffer.len();
+    loop {
+        start -= 1;
+        buffer[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            return &buffer[start..];
+        }
+    }
+}
+
+/// Runs [`Search`]es with the nonces split in blocks across `jobs` threads, saving the progress
+/// to a checkpoint file from time to time so that an interrupted search can resume
+#[derive(Debug, Clone)]
+pub struct Miner {
+    jobs: usize,
+    /// No checkpoints are written when there is no directory
+    checkpoint_dir: Option<PathBuf>,
+}
+
+impl Default for Miner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Miner {
+    /// Miner running on every core and saving its checkpoints to [`get_checkpoint_dir`]
+    pub fn new() -> Self {
+        Self {
+            jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+            checkpoint_dir: Some(get_checkpoint_dir()),
+        }
+    }
+
+    pub fn with_jobs(self, jobs: usize) -> Self {
+        Self {
+            jobs: jobs.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_checkpoint_dir(self, checkpoint_dir: Option<PathBuf>) -> Self {
+        Self {
+            checkpoint_dir,
+            ..self
+        }
+    }
+
+    /// Lowest matching nonce from `start` on
+    pub fn first(&self, search: &Search, start: u64) -> Result<u64> {
+        let nonces = self.first_n(search, start, 1)?;
+        Ok(nonces[0])
+    }
+
+    /// The `n` lowest matching nonces from `start` on, in order
+    pub fn first_n(&self, search: &Search, start: u64, n: usize) -> Result<Vec<u64>> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let path = self
+            .checkpoint_dir
+            .as_ref()
+            .map(|dir| checkpoint_path(dir, search));
+        // Checkpoints only cover the nonces from their start to their next one, and the ones
+        // already holding every nonce asked for belong to a finished search
+        let checkpoint = path
+            .as_deref()
+            .and_then(|path| Checkpoint::load(path, search))
+            .filter(|checkpoint| (checkpoint.start..=checkpoint.next).contains(&start))
+            .filter(|checkpoint| checkpoint.found.iter().filter(|&&f| f >= start).count() < n)
+            .unwrap_or_else(|| Checkpoint {
+                search: search.clone(),
+                start,
+                next: start,
+                found: Vec::new(),
+            });
+        let found: BTreeSet<u64> = checkpoint
+            .found
+            .iter()
+            .copied()
+            .filter(|&nonce| nonce >= start)
+            .collect();
+        let save = |next: u64, found: &BTreeSet<u64>| {
+            if let Some(path) = &path {
+                // The checkpoint still covers the nonces from its own start, along with their matches
+                let earlier = checkpoint
+                    .found
+                    .iter()
+                    .copied()
+                    .filter(|&nonce| nonce < start);
+                let found = earlier.chain(found.range(..next).copied()).collect();
+                Checkpoint {
+                    next,
+                    found,
+                    ..checkpoint.clone()
+                }
+                .save(path);
+            }
+        };
+
+        let from = start.max(checkpoint.next);
+        let (result, next, found) = self.search(search, from, n, found, &save);
+        if let Err(e) = result {
+            if next > from {
+                save(next, &found);
+            }
+            return Err(e);
+        }
+        // Only interrupted searches resume, so every finished one is searched again
+        if let Some(path) = &path {
+            Checkpoint::remove(path);
+        }
+        let nonces: Vec<u64> = found.into_iter().take(n).collect();
+        if nonces.len() < n {
+            return Err(
+                anyhow::anyhow!("Only {} nonces match from {} on", nonces.len(), start).into(),
+            );
+        }
+        Ok(nonces)
+    }
+
+    /// Searches the nonces from `start` on until `found` holds `n` nonces and every nonce below
+    /// the last of them is searched, calling `progress` with the nonce below which everything
+    /// was searched every [`CHECKPOINT_BLOCKS`] blocks. Returns that nonce along with every
+    /// nonce found, including the ones beyond it.
+    fn search(
+        &self,
+        search: &Search,
+        start: u64,
+        n: usize,
+        found: BTreeSet<u64>,
+        progress: &(impl Fn(u64, &BTreeSet<u64>) + Sync),
+    ) -> (Result<()>, u64, BTreeSet<u64>) {
+        let mut prefixed = Hasher::new(search.algorithm);
+        prefixed.update(search.prefix.as_bytes());
+
+        let block_start = |block: u64| start.saturating_add(block.saturating_mul(BLOCK_SIZE));
+        // Nonces from the n-th one found on cannot be among the n lowest
+        let limit = |found: &BTreeSet<u64>| found.iter().nth(n - 1).copied().unwrap_or(u64::MAX);
+        let next_block = AtomicU64::new(0);
+        let bound = AtomicU64::new(limit(&found));
+        let progress_state = Mutex::new(Progress {
+            finished: FinishedBlocks::default(),
+            found,
+        });
+        let token = cancel::current();
+
+        let results: Vec<Result<()>> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.jobs)
+                .map(|_| {
+                    scope.spawn(|| {
+                        token.scope(|| {
+                            loop {
+                                // Blocks are handed out in order, so every block below the
+                                // bound is taken by some worker
+                                let block = next_block.fetch_add(1, Ordering::Relaxed);
+                                let from = block_start(block);
+                                if from >= bound.load(Ordering::Relaxed) {
+                                    return Ok(());
+                                }
+                                cancel::check()?;
+
+                                let matches =
+                                    search.search_block(&prefixed, from..block_start(block + 1));
+                                let mut state = progress_state.lock().unwrap();
+                                state.found.extend(matches);
+                                bound.store(limit(&state.found), Ordering::Relaxed);
+                                let previous = state.finished.frontier;
+                                let frontier = state.finished.finish(block);
+                                if frontier / CHECKPOINT_BLOCKS > previous / CHECKPOINT_BLOCKS {
+                                    progress(block_start(frontier), &state.found);
+                                }
+                            }
+                        })
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).collect()
+        });
+
+        let state = progress_state.into_inner().unwrap();
+        let result = results.into_iter().collect::<Result<()>>();
+        (result, block_start(state.finished.frontier), state.found)
+    }
+}
+
+/// Shared state of the workers of a search
+#[derive(Debug)]
+struct Progress {
+    finished: FinishedBlocks,
+    found: BTreeSet<u64>,
+}
+
+/// Blocks searched so far, as the contiguous run of blocks from the first one plus the ones
+/// finished out of order
+#[derive(Debug, Default)]
+struct FinishedBlocks {
+    /// Every block below it is finished
+    frontier: u64,
+    pending: BTreeSet<u64>,
+}
+
+impl FinishedBlocks {
+    /// Marks the block as finished, returning the new frontier
+    fn finish(&mut self, block: u64) -> u64 {
+        self.pending.insert(block);
+        while self.pending.remove(&self.frontier) {
+            self.frontier += 1;
+        }
+        self.frontier
+    }
+}
+
+/// Progress of a search from `start`, `found` holding every match below `next`, the nonce below
+/// which every nonce was searched
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Checkpoint {
+    search: Search,
+    start: u64,
+    next: u64,
+    found: Vec<u64>,
+}
+
+impl Checkpoint {
+    /// Checkpoint of the search, unless there is none or it cannot be read
+    fn load(path: &Path, search: &Search) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        match serde_json::from_str::<Self>(&content) {
+            Ok(checkpoint) if checkpoint.search == *search => Some(checkpoint),
+            _ => {
+                warn!("Ignoring invalid checkpoint {}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Removes the checkpoint of a search that finished
+    fn remove(path: &Path) {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                warn!("Unable to remove checkpoint {}: {}", path.display(), e)
+            }
+            _ => {}
+        }
+    }
+
+    /// Checkpoints only speed up later searches, so failing to save one is not an error
+    fn save(&self, path: &Path) {
+        // Written to a temporary file first so that interrupting it never leaves half a file
+        let temporary = path.with_extension("tmp");
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&temporary, serde_json::to_string(self)?))
+            .and_then(|_| fs::rename(&temporary, path));
+        if let Err(e) = result {
+            warn!("Unable to save checkpoint {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Directory of the mining checkpoints, taken from [`CHECKPOINT_DIR_ENV`] when it is set
+pub fn get_checkpoint_dir() -> PathBuf {
+    match env::var_os(CHECKPOINT_DIR_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => env::temp_dir().join("aoc-checkpoints"),
+    }
+}
+
+fn checkpoint_path(dir: &Path, search: &Search) -> PathBuf {
+    // The search is hashed so that any prefix makes a valid file name
+    let key = serde_json::to_string(search).unwrap_or_default();
+    dir.join(format!("{}-{:x}.json", search.algorithm, md5::compute(key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn zeros(prefix: &str, algorithm: Algorithm, nibbles: u32) -> Search {
+        Search::new(prefix, algorithm, Predicate::LeadingZeroNibbles(nibbles))
+    }
+
+    #[test]
+    fn test_digest() {
+        let validations = vec![
+            (Algorithm::Md5, "577571be4de9dcce85a041ba0410f29f"),
+            (Algorithm::Sha1, "062c648aaf68174757c50ab1aeebb61e059c1d1b"),
+            (
+                Algorithm::Sha256,
+                "56abfbd7d2ea606e667945422de5a368b8b0272b8f29081cb058b594dd7e3249",
+            ),
+        ];
+
+        for (algorithm, expected_result) in validations.into_iter() {
+            let search = zeros("abc", algorithm, 0);
+            assert_eq!(search.digest(0).to_string(), expected_result);
+        }
+    }
+
+    #[test]
+    fn test_predicates() {
+        let digest = HashDigest::new(&[0x00, 0x0c, 0xaf, 0xe1, 0x11]);
+        let validations = vec![
+            (Predicate::LeadingZeroBits(12), true),
+            (Predicate::LeadingZeroBits(13), false),
+            (Predicate::LeadingZeroNibbles(3), true),
+            (Predicate::LeadingZeroNibbles(4), false),
+            (Predicate::LeadingZeroNibbles(11), false),
+            (Predicate::Contains("cafe".into()), true),
+            (Predicate::Contains("cafa".into()), false),
+            (Predicate::Contains("".into()), true),
+            (Predicate::RepeatedRun(3), true),
+            (Predicate::RepeatedRun(4), false),
+            (Predicate::RepeatedRun(0), true),
+        ];
+
+        for (predicate, expected_result) in validations.into_iter() {
+            assert_eq!(
+                predicate.matches(&digest),
+                expected_result,
+                "{:?}",
+                predicate
+            );
+        }
+    }
+
+    #[test]
+    fn test_first() {
+        let miner = Miner::new().with_jobs(2).with_checkpoint_dir(None);
+        let validations = vec![
+            (zeros("abcdef", Algorithm::Md5, 4), 31556),
+            (zeros("abcdef", Algorithm::Sha1, 4), 185343),
+            (
+                Search::new("abc", Algorithm::Md5, Predicate::LeadingZeroBits(13)),
+                33464,
+            ),
+            (
+                Search::new("abc", Algorithm::Md5, Predicate::Contains("cafe".into())),
+                1884,
+            ),
+        ];
+
+        for (search, expected_result) in validations.into_iter() {
+            assert_eq!(miner.first(&search, 1).unwrap(), expected_result);
+            assert!(search.matches(expected_result));
+        }
+    }
+
+    #[test]
+    fn test_first_n() {
+        let miner = Miner::new().with_jobs(3).with_checkpoint_dir(None);
+        let validations = vec![
+            (
+                zeros("abcdef", Algorithm::Sha256, 4),
+                2,
+                vec![71479, 158058],
+            ),
+            (
+                Search::new("abc", Algorithm::Md5, Predicate::RepeatedRun(5)),
+                3,
+                vec![200, 792, 816],
+            ),
+            (zeros("abcdef", Algorithm::Md5, 4), 0, vec![]),
+        ];
+
+        for (search, n, expected_result) in validations.into_iter() {
+            assert_eq!(miner.first_n(&search, 1, n).unwrap(), expected_result);
+        }
+    }
+
+    #[test]
+    fn test_stretch() {
+        let search =
+            Search::new("abc", Algorithm::Md5, Predicate::RepeatedRun(3)).with_stretch(2016);
+        let matches: Vec<_> = (0..6).filter(|&nonce| search.matches(nonce)).collect();
+        assert_eq!(matches, vec![5]);
+    }
+
+    #[test]
+    fn test_jobs() {
+        let search = zeros("abcdef", Algorithm::Md5, 4);
+        for jobs in [1, 3, 8] {
+            let miner = Miner::new().with_jobs(jobs).with_checkpoint_dir(None);
+            assert_eq!(miner.first(&search, 1).unwrap(), 31556);
+            assert_eq!(miner.first(&search, 31557).unwrap(), 36548);
+        }
+    }
+
+    #[test]
+    fn test_write_decimal() {
+        let mut buffer = [0; 20];
+        for n in [0, 7, 10, 1048970, u64::MAX] {
+            assert_eq!(write_decimal(n, &mut buffer), n.to_string().as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_finished_blocks() {
+        let mut finished = FinishedBlocks::default();
+        assert_eq!(finished.finish(1), 0);
+        assert_eq!(finished.finish(2), 0);
+        assert_eq!(finished.finish(0), 3);
+        assert_eq!(finished.finish(3), 4);
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let dir = checkpoint_dir("checkpoints");
+        let miner = Miner::new().with_checkpoint_dir(Some(dir.clone()));
+        let search = zeros("abcdef", Algorithm::Md5, 4);
+        let path = checkpoint_path(&dir, &search);
+
+        // Finished searches leave no checkpoint behind
+        assert_eq!(miner.first(&search, 1).unwrap(), 31556);
+        assert_eq!(Checkpoint::load(&path, &search), None);
+
+        // Nonces below `next` of an interrupted search are not searched again
+        let interrupted = Checkpoint {
+            search: search.clone(),
+            start: 1,
+            next: 36549,
+            found: vec![],
+        };
+        interrupted.save(&path);
+        assert_eq!(
+            Checkpoint::load(&path, &zeros("abcdef", Algorithm::Md5, 5)),
+            None
+        );
+        assert_eq!(miner.first(&search, 1).unwrap(), 72162);
+        assert_eq!(Checkpoint::load(&path, &search), None);
+
+        // Matches found so far are reused by later starts
+        Checkpoint {
+            found: vec![36548],
+            ..interrupted.clone()
+        }
+        .save(&path);
+        assert_eq!(
+            miner.first_n(&search, 32000, 2).unwrap(),
+            vec![36548, 72162]
+        );
+
+        // Checkpoints already holding the nonces asked for are ignored
+        Checkpoint {
+            next: 100000,
+            found: vec![5],
+            ..interrupted
+        }
+        .save(&path);
+        assert_eq!(miner.first(&search, 1).unwrap(), 31556);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resume_from_lower_start() {
+        let dir = checkpoint_dir("lower-start");
+        let miner = Miner::new()
+            .with_jobs(2)
+            .with_checkpoint_dir(Some(dir.clone()));
+        let search = zeros("abcdef", Algorithm::Md5, 4);
+
+        // Interrupted search from 1, then one from a later nonce interrupted as well
+        Checkpoint {
+            search: search.clone(),
+            start: 1,
+            next: 36549,
+            found: vec![31556, 36548],
+        }
+        .save(&checkpoint_path(&dir, &search));
+        let token = cancel::CancelToken::with_timeout(std::time::Duration::from_millis(100));
+        assert!(token.scope(|| miner.first_n(&search, 32000, 1000)).is_err());
+
+        // One more nonce than the checkpoint holds, so that it is resumed rather than ignored
+        let path = checkpoint_path(&dir, &search);
+        let n = Checkpoint::load(&path, &search).unwrap().found.len() + 1;
+        let expected = Miner::new()
+            .with_checkpoint_dir(None)
+            .first_n(&search, 1, n)
+            .unwrap();
+        assert_eq!(miner.first_n(&search, 1, n).unwrap(), expected);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_on_timeout() {
+        let dir = checkpoint_dir("timeout");
+        let miner = Miner::new()
+            .with_jobs(2)
+            .with_checkpoint_dir(Some(dir.clone()));
+        let search = zeros("pqrstuv", Algorithm::Md5, 8);
+
+        let token = cancel::CancelToken::with_timeout(std::time::Duration::from_millis(500));
+        assert!(token.scope(|| miner.first(&search, 1)).is_err());
+
+        let checkpoint = Checkpoint::load(&checkpoint_path(&dir, &search), &search).unwrap();
+        assert!(checkpoint.found.is_empty());
+        assert!(checkpoint.next > 1);
+        assert_eq!((checkpoint.next - 1) % BLOCK_SIZE, 0);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
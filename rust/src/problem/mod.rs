@@ -1,34 +1,60 @@
-use std::fs;
-
 // mod type_state;
+pub mod selection;
+
+use std::io;
 
 use crate::{
-    error::{AoCError, Result},
-    input, solution,
-    solution::Solution,
+    client::Client,
+    error::Result,
+    input::{self, InputSource, error::InputError},
+    solution::{self, Solution, registry::SolutionInfo},
 };
 
 pub struct Problem {
     year: u16,
     day: u8,
+    info: &'static SolutionInfo,
     solution: Box<dyn Solution>,
 }
 
 impl Problem {
     // add code here
     pub fn new(year: u16, day: u8) -> Result<Self> {
-        let solution = solution::try_get_day_solution(year, day)?;
+        let info = solution::get_solution_info(year, day)?;
 
         Ok(Self {
             year,
             day,
-            solution,
+            info,
+            solution: (info.build)(),
         })
     }
 
+    /// Same problem, solved by another instance of its solution
+    pub fn with_solution(self, solution: Box<dyn Solution>) -> Self {
+        Self { solution, ..self }
+    }
+
+    /// Reads the input of the inputs directory, downloading it first when it is missing. Empty
+    /// files, like the placeholders written by `aoc new`, count as missing.
     pub fn get_default_input(&self) -> Result<String> {
-        let input_path = input::get_default_input_path(self.year, self.day);
-        fs::read_to_string(input_path).map_err(AoCError::IO)
+        let download = || {
+            let path = input::get_default_input_path(self.year, self.day);
+            Ok(Client::from_env()?.cached_input(&path, self.year, self.day)?)
+        };
+        match InputSource::Default.read(self.year, self.day) {
+            Ok(input) if input.is_empty() => download(),
+            Ok(input) => Ok(input),
+            Err(InputError::IOError(e)) if e.kind() == io::ErrorKind::NotFound => download(),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get_input(&self, source: &InputSource) -> Result<String> {
+        match source {
+            InputSource::Default => self.get_default_input(),
+            source => Ok(source.read(self.year, self.day)?),
+        }
     }
 
     // pub fn solve() -> Result<Self> {
@@ -47,6 +73,9 @@ impl Problem {
     pub fn get_day(&self) -> u8 {
         self.day
     }
+    pub fn get_title(&self) -> &'static str {
+        self.info.title
+    }
 
     pub fn get_solution(&self) -> &dyn Solution {
         &*self.solution
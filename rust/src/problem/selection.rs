@@ -0,0 +1,169 @@
+use std::{fmt::Display, str::FromStr};
+
+use clap::ValueEnum;
+
+use crate::solution::{LAST_DAY, Part};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum SelectionError {
+    #[error("Invalid day `{0}`")]
+    InvalidDay(String),
+    #[error("Invalid day range `{0}`")]
+    InvalidRange(String),
+}
+
+/// Parts of a problem to be solved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum PartSelection {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    #[default]
+    Both,
+}
+
+impl PartSelection {
+    pub fn parts(&self) -> Vec<Part> {
+        match self {
+            Self::One => vec![Part::One],
+            Self::Two => vec![Part::Two],
+            Self::Both => vec![Part::One, Part::Two],
+        }
+    }
+}
+
+/// Sorted and deduplicated list of days, parsed from expressions like `1,3,5-9`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection(Vec<u8>);
+
+impl DaySelection {
+    pub fn days(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for DaySelection {
+    fn from(mut days: Vec<u8>) -> Self {
+        days.sort_unstable();
+        days.dedup();
+        Self(days)
+    }
+}
+
+/// Day of an Advent of Code, from 1 to [`LAST_DAY`]
+fn parse_day(s: &str) -> Result<u8, SelectionError> {
+    s.trim()
+        .parse()
+        .ok()
+        .filter(|day| (1..=LAST_DAY).contains(day))
+        .ok_or_else(|| SelectionError::InvalidDay(s.trim().to_string()))
+}
+
+impl FromStr for DaySelection {
+    type Err = SelectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = Vec::new();
+        for item in s.split(',') {
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse_day(start)?, parse_day(end)?);
+                    if start > end {
+                        return Err(SelectionError::InvalidRange(item.trim().to_string()));
+                    }
+                    days.extend(start..=end);
+                }
+                None => days.push(parse_day(item)?),
+            }
+        }
+
+        Ok(Self::from(days))
+    }
+}
+
+/// Writes consecutive days as ranges, e.g. `1,3,5-9`
+impl Display for DaySelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ranges: Vec<(u8, u8)> = Vec::new();
+        for &day in self.0.iter() {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == day => *end = day,
+                _ => ranges.push((day, day)),
+            }
+        }
+        let ranges: Vec<String> = ranges
+            .iter()
+            .map(|&(start, end)| match start == end {
+                true => start.to_string(),
+                false => format!("{}-{}", start, end),
+            })
+            .collect();
+        write!(f, "{}", ranges.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day_selection() {
+        let validations = vec![
+            ("7", vec![7]),
+            ("1,3", vec![1, 3]),
+            ("1,3,5-9", vec![1, 3, 5, 6, 7, 8, 9]),
+            ("5-9, 3,1", vec![1, 3, 5, 6, 7, 8, 9]),
+            ("2-4,3", vec![2, 3, 4]),
+            ("4-4", vec![4]),
+            ("1,25", vec![1, 25]),
+        ];
+
+        for (input, expected_result) in validations.into_iter() {
+            let selection = DaySelection::from_str(input).unwrap();
+            assert_eq!(selection.days(), expected_result.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_day_selection() {
+        let validations = vec![
+            ("", SelectionError::InvalidDay("".into())),
+            ("1,a", SelectionError::InvalidDay("a".into())),
+            ("1-", SelectionError::InvalidDay("".into())),
+            ("9-5", SelectionError::InvalidRange("9-5".into())),
+            ("0", SelectionError::InvalidDay("0".into())),
+            ("24-26", SelectionError::InvalidDay("26".into())),
+        ];
+
+        for (input, expected_result) in validations.into_iter() {
+            assert_eq!(DaySelection::from_str(input), Err(expected_result));
+        }
+    }
+
+    #[test]
+    fn test_display_day_selection() {
+        let validations = vec![
+            (vec![7], "7"),
+            (vec![1, 3], "1,3"),
+            (vec![9, 1, 3, 5, 6, 7, 8], "1,3,5-9"),
+            (vec![10, 11], "10-11"),
+            (vec![], ""),
+        ];
+
+        for (days, expected_result) in validations.into_iter() {
+            let selection = DaySelection::from(days);
+            assert_eq!(selection.to_string(), expected_result);
+            if !selection.days().is_empty() {
+                assert_eq!(DaySelection::from_str(expected_result), Ok(selection));
+            }
+        }
+    }
+
+    #[test]
+    fn test_part_selection() {
+        assert_eq!(PartSelection::One.parts(), vec![Part::One]);
+        assert_eq!(PartSelection::Two.parts(), vec![Part::Two]);
+        assert_eq!(PartSelection::Both.parts(), vec![Part::One, Part::Two]);
+    }
+}
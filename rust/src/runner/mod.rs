@@ -0,0 +1,470 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    sync::{Mutex, mpsc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    error::{self, AoCError},
+    input::{InputSource, diagnostic::Diagnostic},
+    output::{Report, Reporter},
+    problem::{Problem, selection::DaySelection},
+    solution::{
+        self, ParsedInput, Part, answer::Answer, cancel::CancelToken, error::SolutionError,
+    },
+};
+
+/// Outcome of solving a single part of a problem
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PartRecord {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    /// `None` when the part failed
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    /// Location of the input that could not be parsed, when that is the error
+    pub diagnostic: Option<Diagnostic>,
+    /// Time spent parsing the input, shared by every part of the problem
+    pub parse_ns: u64,
+    pub elapsed_ns: u64,
+    /// Whether the part ran out of time, in which case it usually has no answer
+    pub timed_out: bool,
+    pub input: String,
+    /// Known answer of the part for this input, when the answers file has one
+    pub expected: Option<Answer>,
+}
+
+impl PartRecord {
+    /// Record of a part that could not be run at all, e.g. because its input is missing
+    pub fn failed(problem: &Problem, part: Part, input_name: &str, error: String) -> Self {
+        Self {
+            year: problem.get_year(),
+            day: problem.get_day(),
+            part,
+            answer: None,
+            error: Some(error),
+            diagnostic: None,
+            parse_ns: 0,
+            elapsed_ns: 0,
+            timed_out: false,
+            input: input_name.to_string(),
+            expected: None,
+        }
+    }
+
+    /// Whether the part has an answer that differs from the known one
+    pub fn is_wrong(&self) -> bool {
+        self.error.is_none()
+            && self
+                .expected
+                .as_ref()
+                .is_some_and(|expected| Some(expected) != self.answer.as_ref())
+    }
+
+    /// Short status of the part for the summary table
+    pub fn status(&self) -> &'static str {
+        match (self.timed_out, &self.error) {
+            (true, _) => "TIMEOUT",
+            (false, Some(_)) => "ERROR",
+            (false, None) if self.is_wrong() => "WRONG",
+            (false, None) => "OK",
+        }
+    }
+}
+
+/// Maps `items` with `f` on `jobs` worker threads, handing every result to `on_result` on the
+/// calling thread in the order of `items`, as soon as the results before it are done.
+///
+/// Stops handing out new items after the first error of `on_result`, which is returned.
+pub fn map_ordered<T, R, E>(
+    items: Vec<T>,
+    jobs: usize,
+    f: impl Fn(T) -> R + Sync,
+    mut on_result: impl FnMut(R) -> Result<(), E>,
+) -> Result<(), E>
+where
+    T: Send,
+    R: Send,
+{
+    let total = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, total.max(1)) {
+            let (queue, sender, f) = (&queue, sender.clone(), &f);
+            scope.spawn(move || {
+                // The lock is released before `f` runs
+                while let Some((index, item)) = queue.lock().unwrap().next() {
+                    if sender.send((index, f(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in receiver.iter() {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next) {
+                next += 1;
+                if let Err(e) = on_result(result) {
+                    // Leaves nothing for the workers but the items they already took
+                    queue.lock().unwrap().by_ref().for_each(drop);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Problems of the selected days of a year, or of every implemented day when there is no selection
+pub fn select_problems(year: u16, days: Option<&DaySelection>) -> Vec<error::Result<Problem>> {
+    match days {
+        Some(days) => days.days().iter().map(|&d| Problem::new(year, d)).collect(),
+        None => match solution::get_solutions(year).as_slice() {
+            [] => vec![Err(SolutionError::NotImplementedYear { year }.into())],
+            solutions => solutions
+                .iter()
+                .map(|info| Problem::new(info.year, info.day))
+                .collect(),
+        },
+    }
+}
+
+/// Records of a solved problem for each of its inputs, or the error that kept it from being solved
+enum Outcome<R> {
+    Solved(Problem, Vec<(String, Vec<R>)>),
+    Unsolved(AoCError),
+}
+
+/// Solves `parts` of every problem for each of its `input_sources` on `jobs` worker threads, and
+/// reports the records with `reporter` in the order of `problems`.
+///
+/// `failed` builds the records of the parts whose input cannot be read. Unimplemented days are
+/// reported together, by year, once everything else is done.
+pub fn solve_problems<W: Write, R: Report + Send>(
+    problems: Vec<error::Result<Problem>>,
+    parts: &[Part],
+    jobs: usize,
+    mut reporter: Reporter<W, R>,
+    input_sources: impl Fn(&Problem) -> error::Result<Vec<InputSource>> + Sync,
+    solve: impl Fn(&Problem, &[Part], &str, &InputSource) -> Vec<R> + Sync,
+    failed: impl Fn(&Problem, Part, &str, String) -> R + Sync,
+) -> io::Result<Vec<R>> {
+    let solve_input = |problem: &Problem, input_source: &InputSource| {
+        let input_name = input_source.name(problem.get_year(), problem.get_day());
+        let records = match problem.get_input(input_source) {
+            Ok(input) => solve(problem, parts, &input, input_source),
+            Err(e) => parts
+                .iter()
+                .map(|&part| {
+                    let error = format!("Error reading input {}: {}", input_name, e);
+                    failed(problem, part, &input_name, error)
+                })
+                .collect(),
+        };
+        (input_name, records)
+    };
+    let solve_problem =
+        |problem: error::Result<Problem>| match problem.and_then(|p| Ok((input_sources(&p)?, p))) {
+            Ok((input_sources, problem)) => {
+                let inputs = input_sources
+                    .iter()
+                    .map(|source| solve_input(&problem, source))
+                    .collect();
+                Outcome::Solved(problem, inputs)
+            }
+            Err(e) => Outcome::Unsolved(e),
+        };
+
+    let mut unimplemented: BTreeMap<u16, Vec<u8>> = BTreeMap::new();
+    map_ordered(problems, jobs, solve_problem, |outcome| match outcome {
+        Outcome::Solved(problem, inputs) => {
+            reporter.start_problem(&problem)?;
+            let several_inputs = inputs.len() > 1;
+            for (input_name, records) in inputs {
+                if several_inputs {
+                    reporter.start_input(&input_name)?;
+                }
+                records.into_iter().try_for_each(|r| reporter.report(r))?;
+            }
+            Ok(())
+        }
+        Outcome::Unsolved(AoCError::Solution(SolutionError::NotImplementedDay { year, day })) => {
+            unimplemented.entry(year).or_default().push(day);
+            Ok(())
+        }
+        Outcome::Unsolved(e) => reporter.report_error(&e.to_string()),
+    })?;
+
+    for (year, days) in unimplemented {
+        let message = match days.as_slice() {
+            [day] => format!("Day {} of {} is not implemented yet", day, year),
+            _ => format!(
+                "Days {} of {} are not implemented yet",
+                DaySelection::from(days),
+                year
+            ),
+        };
+        reporter.report_error(&message)?;
+    }
+    reporter.finish()
+}
+
+/// Parses the input once and solves every part in `parts` from it, giving each part up to
+/// `timeout` to finish. Only parts calling [`crate::solution::cancel::check`] stop at the timeout, the others run
+/// to the end and are then reported as timed out.
+pub fn solve_problem(
+    problem: &Problem,
+    parts: &[Part],
+    input: &str,
+    input_name: &str,
+    timeout: Option<Duration>,
+) -> Vec<PartRecord> {
+    let current = Instant::now();
+    let parsed = problem.get_solution().parse(input);
+    let parse_ns = current.elapsed().as_nanos() as u64;
+
+    parts
+        .iter()
+        .map(|&part| match &parsed {
+            Ok(parsed) => solve_part(problem, part, parsed, input_name, parse_ns, timeout),
+            Err(e) => PartRecord {
+                parse_ns,
+                diagnostic: diagnostic(e, input_name),
+                ..PartRecord::failed(problem, part, input_name, e.to_string())
+            },
+        })
+        .collect()
+}
+
+fn solve_part(
+    problem: &Problem,
+    part: Part,
+    parsed: &ParsedInput,
+    input_name: &str,
+    parse_ns: u64,
+    timeout: Option<Duration>,
+) -> PartRecord {
+    let token = match timeout {
+        Some(timeout) => CancelToken::with_timeout(timeout),
+        None => CancelToken::new(),
+    };
+    let current = Instant::now();
+    let result = token.scope(|| problem.get_solution().solve_parsed(part, parsed));
+    let elapsed = current.elapsed();
+
+    // Parts that do not check for cancellation still time out when they finish too late
+    let timed_out = timeout.is_some_and(|timeout| elapsed >= timeout);
+    let (answer, error, diagnostic) = match result {
+        Ok(answer) => (Some(answer), None, None),
+        Err(SolutionError::Cancelled) if timed_out => (
+            None,
+            Some(format!(
+                "Exceeded the timeout of {:?}",
+                timeout.unwrap_or_default()
+            )),
+            None,
+        ),
+        Err(e) => (None, Some(e.to_string()), diagnostic(&e, input_name)),
+    };
+
+    PartRecord {
+        year: problem.get_year(),
+        day: problem.get_day(),
+        part,
+        answer,
+        error,
+        diagnostic,
+        parse_ns,
+        elapsed_ns: elapsed.as_nanos() as u64,
+        timed_out,
+        input: input_name.to_string(),
+        expected: None,
+    }
+}
+
+fn diagnostic(error: &SolutionError, input_name: &str) -> Option<Diagnostic> {
+    error.diagnostic().map(|d| d.clone().with_input(input_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{output::OutputFormat, pow::Miner, solution::aoc2015::Day4};
+
+    #[test]
+    fn test_solve_problem() {
+        let problem = Problem::new(2015, 1).unwrap();
+
+        let records = solve_problem(
+            &problem,
+            &[Part::One, Part::Two],
+            "(()))(",
+            "<inline>",
+            None,
+        );
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].year, 2015);
+        assert_eq!(records[0].day, 1);
+        assert_eq!(records[0].part, Part::One);
+        assert_eq!(records[0].answer, Some(Answer::Integer(0)));
+        assert_eq!(records[0].error, None);
+        assert_eq!(records[0].input, "<inline>");
+        assert_eq!(records[1].part, Part::Two);
+        assert_eq!(records[1].answer, Some(Answer::Integer(5)));
+        assert_eq!(records[0].parse_ns, records[1].parse_ns);
+    }
+
+    #[test]
+    fn test_solve_problem_parse_error() {
+        let problem = Problem::new(2015, 9).unwrap();
+
+        let records = solve_problem(
+            &problem,
+            &[Part::One, Part::Two],
+            "London",
+            "<inline>",
+            None,
+        );
+        assert_eq!(records.len(), 2);
+        for record in records {
+            assert_eq!(record.answer, None);
+            assert!(record.error.is_some());
+
+            let diagnostic = record.diagnostic.unwrap();
+            assert_eq!(diagnostic.input.as_deref(), Some("<inline>"));
+            assert_eq!((diagnostic.line, diagnostic.columns), (1, 7..7));
+            assert_eq!(diagnostic.expected.as_deref(), Some("`to`"));
+        }
+    }
+
+    #[test]
+    fn test_solve_problem_timeout() {
+        let day = Day4::with_miner(Miner::new().with_checkpoint_dir(None));
+        let problem = Problem::new(2015, 4).unwrap().with_solution(Box::new(day));
+        let timeout = Duration::from_millis(50);
+
+        let records = solve_problem(&problem, &[Part::Two], "abcdef", "<inline>", Some(timeout));
+        assert_eq!(records.len(), 1);
+        assert!(records[0].timed_out);
+        assert_eq!(records[0].answer, None);
+        assert_eq!(records[0].status(), "TIMEOUT");
+        assert_eq!(
+            records[0].error.as_deref(),
+            Some("Exceeded the timeout of 50ms")
+        );
+        assert!(Duration::from_nanos(records[0].elapsed_ns) < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_solve_problems() {
+        let days: DaySelection = "1,20-21".parse().unwrap();
+        let problems = select_problems(2015, Some(&days));
+        let mut output = Vec::new();
+        let reporter = Reporter::new(OutputFormat::Text, &mut output).unwrap();
+
+        let records = solve_problems(
+            problems,
+            &[Part::One],
+            2,
+            reporter,
+            |_| Ok(vec![InputSource::Inline("(()".into())]),
+            |problem, parts, input, _| solve_problem(problem, parts, input, "<inline>", None),
+            PartRecord::failed,
+        )
+        .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].answer, Some(Answer::Integer(1)));
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("---- 2015 Day 1: "));
+        assert!(output.ends_with("Error: Days 20-21 of 2015 are not implemented yet\n"));
+    }
+
+    #[test]
+    fn test_select_problems() {
+        let problems = select_problems(2015, None);
+        assert_eq!(problems.len(), solution::get_solutions(2015).len());
+        assert!(problems.iter().all(|problem| problem.is_ok()));
+
+        assert!(matches!(
+            select_problems(1999, None).as_slice(),
+            [Err(AoCError::Solution(SolutionError::NotImplementedYear {
+                year: 1999
+            }))]
+        ));
+    }
+
+    #[test]
+    fn test_map_ordered() {
+        for jobs in [0, 1, 3, 16] {
+            let mut results = Vec::new();
+            let items: Vec<u64> = (0..10).rev().collect();
+            let outcome: Result<(), ()> = map_ordered(
+                items,
+                jobs,
+                |i| {
+                    // Later items finish first
+                    thread::sleep(std::time::Duration::from_millis(i * 2));
+                    i * i
+                },
+                |r| {
+                    results.push(r);
+                    Ok(())
+                },
+            );
+            assert_eq!(outcome, Ok(()));
+            assert_eq!(results, vec![81, 64, 49, 36, 25, 16, 9, 4, 1, 0]);
+        }
+
+        let mut results = Vec::new();
+        let outcome = map_ordered(
+            (0..100).collect(),
+            2,
+            |i: u32| i,
+            |r| {
+                if r == 3 {
+                    return Err("stop");
+                }
+                results.push(r);
+                Ok(())
+            },
+        );
+        assert_eq!(outcome, Err("stop"));
+        assert_eq!(results, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_failed_part() {
+        let problem = Problem::new(2015, 9).unwrap();
+
+        let record = PartRecord::failed(&problem, Part::Two, "day9.txt", "missing".into());
+        assert_eq!(record.answer, None);
+        assert_eq!(record.error, Some("missing".to_string()));
+        assert_eq!(record.diagnostic, None);
+        assert_eq!(record.status(), "ERROR");
+    }
+
+    #[test]
+    fn test_wrong_part() {
+        let problem = Problem::new(2015, 1).unwrap();
+        let mut record = solve_problem(&problem, &[Part::One], "(()", "<inline>", None).remove(0);
+        assert_eq!(record.status(), "OK");
+
+        record.expected = Some(Answer::Integer(1));
+        assert!(!record.is_wrong());
+        record.expected = Some(Answer::Text("1".into()));
+        assert!(record.is_wrong());
+        assert_eq!(record.status(), "WRONG");
+    }
+}
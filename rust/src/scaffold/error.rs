@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+pub type Result<T> = core::result::Result<T, ScaffoldError>;
+
+#[derive(thiserror::Error, Debug)]
+#[error("Scaffold Error")]
+pub enum ScaffoldError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("Day {day} is not a valid Advent of Code")]
+    InvalidDay { day: u8 },
+    #[error("{} already exists", .0.display())]
+    AlreadyExists(PathBuf),
+    #[error("Unable to find where to register the solution in {}", .0.display())]
+    Registration(PathBuf),
+}
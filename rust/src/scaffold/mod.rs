@@ -0,0 +1,293 @@
+pub mod error;
+/// Day rendered from the template, checked in so that the tests compile what `aoc new` writes
+#[cfg(test)]
+mod rendered_day;
+
+use error::{Result, ScaffoldError};
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{input, solution::LAST_DAY};
+
+/// Sources of this repository, resolved at build time
+pub const SOURCE_BASE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/");
+
+const DAY_TEMPLATE: &str = include_str!("templates/day.rs.tmpl");
+const YEAR_TEMPLATE: &str = include_str!("templates/year.rs.tmpl");
+
+/// Writes the module of a new day from the template, registers it in its year module and creates
+/// an empty input placeholder. The year module is created and registered when it is missing.
+///
+/// Returns the files that were created or modified.
+pub fn scaffold_day(
+    source_dir: &Path,
+    input_dir: &Path,
+    year: u16,
+    day: u8,
+    title: &str,
+) -> Result<Vec<PathBuf>> {
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(ScaffoldError::InvalidDay { day });
+    }
+
+    let solution_dir = source_dir.join("solution");
+    let day_path = solution_dir
+        .join(format!("aoc{}", year))
+        .join(format!("day{}.rs", day));
+    if day_path.exists() {
+        return Err(ScaffoldError::AlreadyExists(day_path));
+    }
+
+    let mut touched = Vec::new();
+    let year_path = solution_dir.join(format!("aoc{}.rs", year));
+    if !year_path.exists() {
+        let registry_path = solution_dir.join("mod.rs");
+        let solution_module = fs::read_to_string(&registry_path)?;
+        let solution_module = register_year(&solution_module, year)
+            .ok_or_else(|| ScaffoldError::Registration(registry_path.clone()))?;
+
+        fs::write(&year_path, YEAR_TEMPLATE)?;
+        fs::write(&registry_path, solution_module)?;
+        touched.extend([year_path.clone(), registry_path]);
+    }
+
+    let year_module = fs::read_to_string(&year_path)?;
+    let year_module = register_day(&year_module, day)
+        .ok_or_else(|| ScaffoldError::Registration(year_path.clone()))?;
+    fs::create_dir_all(day_path.parent().unwrap())?;
+    fs::write(&day_path, render_day(year, day, title))?;
+    fs::write(&year_path, year_module)?;
+    if !touched.contains(&year_path) {
+        touched.push(year_path);
+    }
+    touched.push(day_path);
+
+    let input_path = input::get_input_path(input_dir, year, day);
+    if !input_path.exists() {
+        fs::create_dir_all(input_path.parent().unwrap())?;
+        fs::write(&input_path, "")?;
+        touched.push(input_path);
+    }
+
+    Ok(touched)
+}
+
+fn render_day(year: u16, day: u8, title: &str) -> String {
+    DAY_TEMPLATE
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+        .replace(
+            "{{title}}",
+            &title.replace('\\', "\\\\").replace('"', "\\\""),
+        )
+}
+
+/// Declares the module of the day in a year module and adds it to its `year_solutions!` list,
+/// keeping both in day order
+fn register_day(year_module: &str, day: u8) -> Option<String> {
+    let re = Regex::new(r"(?s)year_solutions! \{(?P<entries>[^}]*)\}").unwrap();
+    let entries = re.captures(year_module)?.name("entries")?;
+
+    let entry_re = Regex::new(r"day(?P<day>\d+)::Day\d+").unwrap();
+    let mut days: Vec<u8> = entry_re
+        .captures_iter(entries.as_str())
+        .filter_map(|c| c["day"].parse().ok())
+        .collect();
+    if days.contains(&day) {
+        return None;
+    }
+    days.push(day);
+    days.sort();
+
+    let entries_block: String = days
+        .iter()
+        .map(|d| format!("\n    day{d}::Day{d},"))
+        .chain(["\n".to_string()])
+        .collect();
+    let module = format!(
+        "{}{}{}",
+        &year_module[..entries.start()],
+        entries_block,
+        &year_module[entries.end()..]
+    );
+
+    // The declarations are rewritten as a whole, or added right before the list
+    let mods_block: String = days.iter().map(|d| format!("mod day{d};\n")).collect();
+    let mods_re = Regex::new(r"(?m)^(?:mod day\d+;\n)+").unwrap();
+    match mods_re.find(&module) {
+        Some(mods) => Some(format!(
+            "{}{}{}",
+            &module[..mods.start()],
+            mods_block,
+            &module[mods.end()..]
+        )),
+        None => {
+            let list = module.find("year_solutions! {")?;
+            Some(format!(
+                "{}{}\n{}",
+                &module[..list],
+                mods_block,
+                &module[list..]
+            ))
+        }
+    }
+}
+
+/// Declares the module of the year in the solution module and adds it to the registry
+fn register_year(solution_module: &str, year: u16) -> Option<String> {
+    let list_re =
+        Regex::new(r"\[(?P<years>aoc\d{4}::SOLUTIONS(?:,\s*aoc\d{4}::SOLUTIONS)*)\]").unwrap();
+    let years_match = list_re.captures(solution_module)?.name("years")?;
+
+    let year_re = Regex::new(r"aoc(?P<year>\d{4})").unwrap();
+    let mut years: Vec<u16> = year_re
+        .captures_iter(years_match.as_str())
+        .filter_map(|c| c["year"].parse().ok())
+        .collect();
+    if years.contains(&year) {
+        return None;
+    }
+    years.push(year);
+    years.sort();
+    let years_list = years
+        .iter()
+        .map(|y| format!("aoc{y}::SOLUTIONS"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut module = format!(
+        "{}{}{}",
+        &solution_module[..years_match.start()],
+        years_list,
+        &solution_module[years_match.end()..]
+    );
+
+    let mod_re = Regex::new(r"(?m)^pub mod aoc(?P<year>\d{4});\n").unwrap();
+    let insert_at = mod_re
+        .captures_iter(&module)
+        .filter(|c| c["year"].parse::<u16>().is_ok_and(|y| y < year))
+        .last()
+        .map(|c| c.get(0).unwrap().end())
+        .or_else(|| mod_re.find(&module).map(|m| m.start()))?;
+    module.insert_str(insert_at, &format!("pub mod aoc{};\n", year));
+
+    Some(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::error::SolutionError;
+
+    const SOLUTION_MODULE: &str = "pub mod aoc2015;\npub mod error;\n\n\
+        const YEARS: &[&[SolutionInfo]] = &[aoc2015::SOLUTIONS];\n";
+
+    fn temp_tree(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/solution")).unwrap();
+        fs::write(root.join("src/solution/mod.rs"), SOLUTION_MODULE).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_register_day() {
+        let module = "use x;\n\nmod day1;\nmod day3;\n\n\
+            year_solutions! {\n    day1::Day1,\n    day3::Day3,\n}\n";
+        let expected = "use x;\n\nmod day1;\nmod day2;\nmod day3;\n\n\
+            year_solutions! {\n    day1::Day1,\n    day2::Day2,\n    day3::Day3,\n}\n";
+        assert_eq!(register_day(module, 2).as_deref(), Some(expected));
+        assert_eq!(register_day(module, 3), None);
+
+        let module = register_day(YEAR_TEMPLATE, 7).unwrap();
+        assert!(module.ends_with("\n\nmod day7;\n\nyear_solutions! {\n    day7::Day7,\n}\n"));
+    }
+
+    #[test]
+    fn test_register_year() {
+        let module = register_year(SOLUTION_MODULE, 2016).unwrap();
+        assert!(module.starts_with("pub mod aoc2015;\npub mod aoc2016;\npub mod error;\n"));
+        assert!(module.contains("&[aoc2015::SOLUTIONS, aoc2016::SOLUTIONS];"));
+
+        let module = register_year(&module, 2014).unwrap();
+        assert!(module.starts_with("pub mod aoc2014;\npub mod aoc2015;\npub mod aoc2016;\n"));
+        assert!(module.contains("&[aoc2014::SOLUTIONS, aoc2015::SOLUTIONS, aoc2016::SOLUTIONS];"));
+
+        assert_eq!(register_year(SOLUTION_MODULE, 2015), None);
+        assert_eq!(register_year("pub mod error;\n", 2016), None);
+    }
+
+    #[test]
+    fn test_render_day() {
+        let day = render_day(2016, 4, "Security \"Through\" Obscurity");
+
+        assert!(day.contains("pub struct Day4 {}"));
+        assert!(day.contains(
+            r#"register_solution!(Day4, year: 2016, day: 4, title: "Security \"Through\" Obscurity");"#
+        ));
+        assert!(day.contains("NotImplementedDay { year: 2016, day: 4 }"));
+        assert!(!day.contains("{{"));
+    }
+
+    #[test]
+    fn test_rendered_day() {
+        // Rendering again catches a template changed without updating the compiled copy
+        let expected = include_str!("rendered_day.rs");
+        assert_eq!(render_day(2016, 1, "No Time for a Taxicab"), expected);
+
+        let day = (rendered_day::Day1::INFO.build)();
+        assert!(matches!(
+            day.part1("R2"),
+            Err(SolutionError::NotImplementedDay { year: 2016, day: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_scaffold_day() {
+        let root = temp_tree("day");
+        let (source_dir, input_dir) = (root.join("src"), root.join("inputs"));
+
+        let touched = scaffold_day(&source_dir, &input_dir, 2016, 2, "Bathroom Security").unwrap();
+        assert_eq!(
+            touched,
+            vec![
+                source_dir.join("solution/aoc2016.rs"),
+                source_dir.join("solution/mod.rs"),
+                source_dir.join("solution/aoc2016/day2.rs"),
+                input_dir.join("2016/day2.txt"),
+            ]
+        );
+        assert_eq!(fs::read_to_string(&touched[3]).unwrap(), "");
+
+        fs::write(input_dir.join("2016/day1.txt"), "R2, L3").unwrap();
+        let touched = scaffold_day(&source_dir, &input_dir, 2016, 1, "Taxicab").unwrap();
+        assert_eq!(
+            touched,
+            vec![
+                source_dir.join("solution/aoc2016.rs"),
+                source_dir.join("solution/aoc2016/day1.rs"),
+            ]
+        );
+        let input = fs::read_to_string(input_dir.join("2016/day1.txt")).unwrap();
+        assert_eq!(input, "R2, L3");
+
+        let year_module = fs::read_to_string(source_dir.join("solution/aoc2016.rs")).unwrap();
+        assert!(year_module.contains("mod day1;\nmod day2;\n\nyear_solutions! {"));
+        assert!(year_module.contains("year_solutions! {\n    day1::Day1,\n    day2::Day2,\n}"));
+
+        assert!(matches!(
+            scaffold_day(&source_dir, &input_dir, 2016, 1, ""),
+            Err(ScaffoldError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            scaffold_day(&source_dir, &input_dir, 2016, 26, ""),
+            Err(ScaffoldError::InvalidDay { day: 26 })
+        ));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+use crate::solution::{PartResult, Solution, error::SolutionError, registry::register_solution};
+
+#[derive(Debug, Clone)]
+pub struct Day1 {}
+
+impl Day1 {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+register_solution!(Day1, year: 2016, day: 1, title: "No Time for a Taxicab");
+
+impl Solution for Day1 {
+    fn part1(&self, _input: &str) -> PartResult {
+        Err(SolutionError::NotImplementedDay { year: 2016, day: 1 })
+    }
+
+    fn part2(&self, _input: &str) -> PartResult {
+        Err(SolutionError::NotImplementedDay { year: 2016, day: 1 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::answer::Answer;
+
+    #[test]
+    #[ignore = "the examples of part 1 are not filled in yet"]
+    fn test_part_1_examples() {
+        let validations: Vec<(&str, Answer)> = vec![("", Answer::NoSolution)];
+
+        for (input, expected_result) in validations.into_iter() {
+            let result = Day1::new().part1(input).unwrap();
+
+            assert_eq!(result, expected_result);
+        }
+    }
+
+    #[test]
+    #[ignore = "the examples of part 2 are not filled in yet"]
+    fn test_part_2_examples() {
+        let validations: Vec<(&str, Answer)> = vec![("", Answer::NoSolution)];
+
+        for (input, expected_result) in validations.into_iter() {
+            let result = Day1::new().part2(input).unwrap();
+
+            assert_eq!(result, expected_result);
+        }
+    }
+}
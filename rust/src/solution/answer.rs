@@ -0,0 +1,212 @@
+use std::fmt::Display;
+
+use serde::{
+    Deserialize, Serialize,
+    de::{self, SeqAccess, Visitor},
+};
+
+/// Answer of a part. In JSON and in the answers files integers are numbers, text is a string,
+/// multi-line text is an array of lines and no solution is `null`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// Wide enough for every answer that fits in a primitive integer type
+    Integer(i128),
+    Text(String),
+    /// Text spanning several lines, such as letters drawn in ASCII art
+    Lines(Vec<String>),
+    /// The input has no solution
+    NoSolution,
+}
+
+impl Answer {
+    /// Answer given as a line of text, such as a submitted one, which is an integer when it
+    /// reads as one
+    pub fn from_line(line: &str) -> Self {
+        let line = line.trim();
+        line.parse::<i128>()
+            .map_or_else(|_| line.into(), Self::Integer)
+    }
+
+    /// Lines of the answer as shown to the user
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            Self::Lines(lines) => lines.clone(),
+            answer => vec![answer.to_string()],
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Text(text) => write!(f, "{}", text),
+            Self::Lines(lines) => write!(f, "{}", lines.join("\n")),
+            Self::NoSolution => write!(f, "No solution"),
+        }
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            // Written as the narrowest type so that every JSON and TOML reader gets a plain number
+            Self::Integer(value) => match (i64::try_from(*value), u64::try_from(*value)) {
+                (Ok(value), _) => serializer.serialize_i64(value),
+                (_, Ok(value)) => serializer.serialize_u64(value),
+                _ => serializer.serialize_i128(*value),
+            },
+            Self::Text(text) => serializer.serialize_str(text),
+            Self::Lines(lines) => lines.serialize(serializer),
+            Self::NoSolution => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+struct AnswerVisitor;
+
+impl<'de> Visitor<'de> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "an integer, a string, an array of lines or null")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Answer, E> {
+        Ok(value.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Answer, E> {
+        Ok(value.into())
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<Answer, E> {
+        Ok(value.into())
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Answer, E> {
+        Ok(text.into())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Answer, A::Error> {
+        let mut lines = Vec::new();
+        while let Some(line) = seq.next_element()? {
+            lines.push(line);
+        }
+        Ok(Answer::Lines(lines))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Answer, E> {
+        Ok(Answer::NoSolution)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Answer, E> {
+        Ok(Answer::NoSolution)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Self::Lines(lines)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    /// `None` stands for an input without solution
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::NoSolution, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        let validations = vec![
+            (Answer::from(42u8), Answer::Integer(42)),
+            (Answer::from(-3isize), Answer::Integer(-3)),
+            (Answer::from(u64::MAX), Answer::Integer(u64::MAX as i128)),
+            (Answer::from("abc"), Answer::Text("abc".into())),
+            (Answer::from(Some(7usize)), Answer::Integer(7)),
+            (Answer::from(None::<usize>), Answer::NoSolution),
+            (Answer::from_line(" -12\n"), Answer::Integer(-12)),
+            (Answer::from_line("12a"), Answer::Text("12a".into())),
+        ];
+
+        for (input, expected_result) in validations.into_iter() {
+            assert_eq!(input, expected_result);
+        }
+    }
+
+    #[test]
+    fn test_display_answer() {
+        let validations = vec![
+            (Answer::Integer(-12), "-12", vec!["-12"]),
+            (Answer::Text("abc".into()), "abc", vec!["abc"]),
+            (
+                Answer::Lines(vec!["#.".into(), ".#".into()]),
+                "#.\n.#",
+                vec!["#.", ".#"],
+            ),
+            (Answer::NoSolution, "No solution", vec!["No solution"]),
+        ];
+
+        for (input, expected_result, expected_lines) in validations.into_iter() {
+            assert_eq!(input.to_string(), expected_result);
+            assert_eq!(input.lines(), expected_lines);
+        }
+    }
+
+    #[test]
+    fn test_serialize_answer() {
+        let validations = vec![
+            (Answer::Integer(1342), "1342"),
+            (Answer::Text("1342".into()), "\"1342\""),
+            (Answer::Lines(vec!["2".into(), "4".into()]), "[\"2\",\"4\"]"),
+            (Answer::NoSolution, "null"),
+            (Answer::Integer(u64::MAX as i128), "18446744073709551615"),
+        ];
+
+        for (input, expected_result) in validations.into_iter() {
+            assert_eq!(serde_json::to_string(&input).unwrap(), expected_result);
+            assert_eq!(
+                serde_json::from_str::<Answer>(expected_result).unwrap(),
+                input
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::solution::{PartResult, Solution};
+use crate::solution::{PartResult, Solution, registry::register_solution};
 
 #[derive(Debug, Clone)]
 pub struct Day1 {}
@@ -9,22 +9,24 @@ impl Day1 {
     }
 }
 
+register_solution!(Day1, year: 2015, day: 1, title: "Not Quite Lisp");
+
 impl Solution for Day1 {
     fn part1(&self, input: &str) -> PartResult {
         let ups = input.matches("(").count();
         let downs = input.matches(")").count();
 
-        let floor = ups - downs;
+        let floor = ups as isize - downs as isize;
 
-        Ok(vec![floor.to_string()])
+        Ok(floor.into())
     }
 
     fn part2(&self, input: &str) -> PartResult {
         let expected_floor = -1;
 
         let mut floor = 0;
-        let mut idx = 0;
-        for (_idx, char) in input.chars().enumerate() {
+        let mut position = None;
+        for (idx, char) in input.chars().enumerate() {
             match char {
                 '(' => {
                     floor += 1;
@@ -35,11 +37,35 @@ impl Solution for Day1 {
                 _ => (),
             };
             if floor == expected_floor {
-                idx = _idx;
+                // Positions start at 1
+                position = Some(idx + 1);
                 break;
             }
         }
 
-        Ok(vec![idx.to_string()])
+        // Santa may never enter the basement
+        Ok(position.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::answer::Answer;
+
+    #[test]
+    fn test_basement_position() {
+        let validations = vec![
+            (")", Answer::Integer(1)),
+            ("()())", Answer::Integer(5)),
+            ("(()", Answer::NoSolution),
+            ("", Answer::NoSolution),
+        ];
+
+        for (input, expected_result) in validations.into_iter() {
+            let result = Day1::new().part2(input).unwrap();
+
+            assert_eq!(result, expected_result);
+        }
     }
 }
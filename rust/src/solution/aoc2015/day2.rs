@@ -1,8 +1,12 @@
-use std::{fmt::Display, str::FromStr};
+use std::str::FromStr;
 
 use crate::{
-    input,
-    solution::{PartResult, Solution},
+    input::{
+        self,
+        diagnostic::{self, Diagnostic},
+        error::InputError,
+    },
+    solution::{PartResult, TypedSolution, error::Result, registry::register_solution},
 };
 
 #[derive(Debug, Clone)]
@@ -14,8 +18,10 @@ impl Day2 {
     }
 }
 
+register_solution!(Day2, year: 2015, day: 2, title: "I Was Told There Would Be No Math");
+
 #[derive(Debug)]
-struct Package {
+pub struct Package {
     l: usize,
     w: usize,
     h: usize,
@@ -36,42 +42,41 @@ impl Package {
     }
 }
 
-impl<T: FromStr + Display> From<T> for Package {
-    fn from(string_like: T) -> Self {
-        let [l, w, h]: [usize; 3] = input::split_line::<usize>(&string_like.to_string(), 'x')
-            .unwrap()
-            .try_into()
-            .unwrap_or_else(|_| panic!("Error parsing package: {}", string_like));
+impl FromStr for Package {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let [l, w, h]: [usize; 3] = input::split_line(s, 'x')?.try_into().map_err(|_| {
+            Diagnostic::expected(s, diagnostic::columns(s, 0..s.len()), "`<l>x<w>x<h>`")
+        })?;
 
-        Self::new(l, w, h)
+        Ok(Self::new(l, w, h))
     }
 }
 
-impl Solution for Day2 {
-    fn part1(&self, input: &str) -> PartResult {
-        let lines = input::parse_input_lines::<String>(input).unwrap();
+impl TypedSolution for Day2 {
+    type Parsed = Vec<Package>;
 
-        let packages: Vec<Package> = lines.into_iter().map(Package::from).collect();
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input::parse_input_lines_with(input, Package::from_str)?)
+    }
 
+    fn part1(&self, packages: &Self::Parsed) -> PartResult {
         let total_surface: usize = packages
             .iter()
             .map(|package| package.required_surface())
             .sum();
 
-        Ok(vec![total_surface.to_string()])
+        Ok(total_surface.into())
     }
 
-    fn part2(&self, input: &str) -> PartResult {
-        let lines = input::parse_input_lines::<String>(input).unwrap();
-
-        let packages: Vec<Package> = lines.into_iter().map(Package::from).collect();
-
+    fn part2(&self, packages: &Self::Parsed) -> PartResult {
         let total_ribon: usize = packages
             .iter()
             .map(|package| package.required_ribon())
             .sum();
 
-        Ok(vec![total_ribon.to_string()])
+        Ok(total_ribon.into())
     }
 }
 
@@ -83,10 +88,10 @@ mod tests {
 
     #[test]
     fn required_package_surface() {
-        let validations = vec![("2x3x4".to_string(), 58), ("1x1x10".to_string(), 43)];
+        let validations = vec![("2x3x4", 58), ("1x1x10", 43)];
 
         for (dimensions, expected) in validations.into_iter() {
-            let package = Package::from(dimensions);
+            let package = Package::from_str(dimensions).unwrap();
 
             assert_eq!(package.required_surface(), expected);
         }
@@ -94,12 +99,25 @@ mod tests {
 
     #[test]
     fn required_package_ribon() {
-        let validations = vec![("2x3x4".to_string(), 34), ("1x1x10".to_string(), 14)];
+        let validations = vec![("2x3x4", 34), ("1x1x10", 14)];
 
         for (dimensions, expected) in validations.into_iter() {
-            let package = Package::from(dimensions);
+            let package = Package::from_str(dimensions).unwrap();
 
             assert_eq!(package.required_ribon(), expected);
         }
     }
+
+    #[test]
+    fn malformed_packages() {
+        let validations = vec![("2x3", 1..4), ("2x3x4x5", 1..8), ("2x3xa", 5..6)];
+
+        for (dimensions, expected_columns) in validations.into_iter() {
+            let error = Package::from_str(dimensions).unwrap_err();
+            assert_eq!(error.diagnostic().unwrap().columns, expected_columns);
+        }
+
+        let error = <Day2 as TypedSolution>::parse("2x3x4\n1x1").unwrap_err();
+        assert_eq!(error.diagnostic().unwrap().line, 2);
+    }
 }
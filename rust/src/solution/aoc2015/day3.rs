@@ -1,8 +1,8 @@
 use std::collections::HashSet;
 
 use crate::{
-    input,
-    solution::{PartResult, Solution},
+    input::{self, diagnostic::Diagnostic, error::Result},
+    solution::{PartResult, Solution, answer::Answer, registry::register_solution},
 };
 
 #[derive(Debug, Clone)]
@@ -14,32 +14,45 @@ impl Day3 {
     }
 }
 
+register_solution!(Day3, year: 2015, day: 3, title: "Perfectly Spherical Houses in a Vacuum");
+
 #[derive(Eq, PartialEq, Hash, Clone, Copy)]
 struct HousePosition {
     x: isize,
     y: isize,
 }
 
-fn houses_visited_by_santa(input: &str) -> usize {
+/// Move of a single instruction, found at the 1-based `column` of the line
+fn direction(line: &str, column: usize, instruction: char) -> Result<(isize, isize)> {
+    match instruction {
+        '>' => Ok((1, 0)),
+        '<' => Ok((-1, 0)),
+        '^' => Ok((0, 1)),
+        'v' => Ok((0, -1)),
+        _ => Err(Diagnostic::expected(
+            line,
+            column..column + 1,
+            "one of `>`, `<`, `^` or `v`",
+        ))?,
+    }
+}
+
+fn houses_visited_by_santa(input: &str) -> Result<usize> {
     let mut santa_pos = HousePosition { x: 0, y: 0 };
     let mut visited_houses = HashSet::new();
 
     visited_houses.insert(santa_pos);
 
-    for char in input.chars() {
-        match char {
-            '>' => santa_pos.x += 1,
-            '<' => santa_pos.x -= 1,
-            '^' => santa_pos.y += 1,
-            'v' => santa_pos.y -= 1,
-            _ => panic!("wrong instructions"),
-        }
+    for (idx, char) in input.chars().enumerate() {
+        let (dx, dy) = direction(input, idx + 1, char)?;
+        santa_pos.x += dx;
+        santa_pos.y += dy;
         visited_houses.insert(santa_pos);
     }
-    visited_houses.len()
+    Ok(visited_houses.len())
 }
 
-fn houses_visited_by_robo_santa(input: &str) -> usize {
+fn houses_visited_by_robo_santa(input: &str) -> Result<usize> {
     let mut santa_pos = HousePosition { x: 0, y: 0 };
     let mut robo_santa_pos = HousePosition { x: 0, y: 0 };
 
@@ -53,35 +66,33 @@ fn houses_visited_by_robo_santa(input: &str) -> usize {
         } else {
             actor_ptr = &mut robo_santa_pos;
         }
-        match char {
-            '>' => actor_ptr.x += 1,
-            '<' => actor_ptr.x -= 1,
-            '^' => actor_ptr.y += 1,
-            'v' => actor_ptr.y -= 1,
-            _ => panic!("wrong instructions"),
-        }
+        let (dx, dy) = direction(input, idx + 1, char)?;
+        actor_ptr.x += dx;
+        actor_ptr.y += dy;
         visited_houses.insert(*actor_ptr);
     }
-    visited_houses.len()
+    Ok(visited_houses.len())
+}
+
+/// Every line of the input is a separate list of instructions, with an answer of its own
+fn answer_per_line(houses: Vec<usize>) -> Answer {
+    match houses.as_slice() {
+        [count] => (*count).into(),
+        _ => Answer::Lines(houses.iter().map(|count| count.to_string()).collect()),
+    }
 }
 
 impl Solution for Day3 {
     fn part1(&self, input: &str) -> PartResult {
-        let lines = input::parse_input_lines::<String>(input).unwrap();
+        let houses = input::parse_input_lines_with(input, houses_visited_by_santa)?;
 
-        Ok(lines
-            .into_iter()
-            .map(|line| houses_visited_by_santa(&line).to_string())
-            .collect())
+        Ok(answer_per_line(houses))
     }
 
     fn part2(&self, input: &str) -> PartResult {
-        let lines = input::parse_input_lines::<String>(input).unwrap();
+        let houses = input::parse_input_lines_with(input, houses_visited_by_robo_santa)?;
 
-        Ok(lines
-            .into_iter()
-            .map(|line| houses_visited_by_robo_santa(&line).to_string())
-            .collect())
+        Ok(answer_per_line(houses))
     }
 }
 
@@ -94,7 +105,7 @@ mod tests {
         let validations = vec![(">", 2), ("^>v<", 4), ("^v^v^v^v^v", 2)];
 
         for (input, expected_result) in validations.into_iter() {
-            let result = houses_visited_by_santa(input);
+            let result = houses_visited_by_santa(input).unwrap();
 
             assert_eq!(result, expected_result);
         }
@@ -105,9 +116,19 @@ mod tests {
         let validations = vec![("^v", 3), ("^>v<", 3), ("^v^v^v^v^v", 11)];
 
         for (input, expected_result) in validations.into_iter() {
-            let result = houses_visited_by_robo_santa(input);
+            let result = houses_visited_by_robo_santa(input).unwrap();
 
             assert_eq!(result, expected_result);
         }
     }
+
+    #[test]
+    fn test_wrong_instructions() {
+        let error = houses_visited_by_santa("^>x<").unwrap_err();
+        assert_eq!(error.diagnostic().unwrap().columns, 3..4);
+
+        let error = Day3::new().part2("^v\n^v^v^ v").unwrap_err();
+        let diagnostic = error.diagnostic().unwrap();
+        assert_eq!((diagnostic.line, diagnostic.columns.start), (2, 6));
+    }
 }
@@ -1,104 +1,86 @@
-use std::{cell::RefCell, collections::HashMap};
-
 use crate::{
-    input,
-    solution::{PartResult, Solution},
+    input::{self, diagnostic::Diagnostic, error::InputError},
+    pow::{Algorithm, Miner, Predicate, Search},
+    solution::{PartResult, Solution, error::Result, registry::register_solution},
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Day4 {
-    hashes: RefCell<HashMap<usize, usize>>,
+    miner: Miner,
 }
 
 impl Day4 {
     pub fn new() -> Self {
-        Self {
-            hashes: RefCell::new(HashMap::default()),
-        }
+        Self::with_miner(Miner::new())
     }
-}
 
-impl Day4 {
-    fn find_hash_integer<T>(&self, input: &String, difficulty: T) -> usize
-    where
-        T: Into<usize> + Copy,
-    {
-        let difficulty = &difficulty.into();
-        if self.hashes.borrow().contains_key(difficulty) {
-            return *self.hashes.borrow().get(difficulty).unwrap();
-        }
-
-        let max_difficulty = match &self
-            .hashes
-            .borrow()
-            .keys()
-            .filter(|&k| k <= difficulty)
-            .max()
-        {
-            &Some(&v) => v,
-            None => 1,
-        };
-
-        let mut current_lower = match self.hashes.borrow().get(&max_difficulty) {
-            Some(&v) => v,
-            None => 1,
-        };
+    pub fn with_miner(miner: Miner) -> Self {
+        Self { miner }
+    }
+}
 
-        let mut seed: String;
-        let needle = "0".repeat(*difficulty);
-        loop {
-            seed = format!("{input}{current_lower}");
-            let hash = md5::compute(&seed);
+register_solution!(Day4, year: 2015, day: 4, title: "The Ideal Stocking Stuffer");
 
-            if format!("{hash:x}").starts_with(&needle) {
-                break;
-            }
-            current_lower += 1;
-        }
-        self.hashes.borrow_mut().insert(*difficulty, current_lower);
+impl Day4 {
+    fn find_hash_integer(&self, key: &str, zeros: usize) -> Result<u64> {
+        let search = Search::new(
+            key,
+            Algorithm::Md5,
+            Predicate::LeadingZeroNibbles(zeros as u32),
+        );
+        self.miner.first(&search, 1)
+    }
+}
 
-        current_lower
+fn secret_key(input: &str) -> Result<String> {
+    let lines = input::parse_input_lines::<String>(input)?;
+    match lines.first() {
+        Some(key) if !key.is_empty() => Ok(key.clone()),
+        _ => Err(InputError::from(Diagnostic::expected("", 1..1, "a secret key")).into()),
     }
 }
 
 impl Solution for Day4 {
     fn part1(&self, input: &str) -> PartResult {
-        let base_key = input::parse_input_lines::<String>(input)
-            .unwrap()
-            .first()
-            .expect("Unable to read input")
-            .clone();
+        let base_key = secret_key(input)?;
 
         let difficulty = 5;
-        let k = self.find_hash_integer::<usize>(&base_key, difficulty);
-        Ok(vec![k.to_string()])
+        let k = self.find_hash_integer(&base_key, difficulty)?;
+        Ok(k.into())
     }
 
     fn part2(&self, input: &str) -> PartResult {
-        let base_key = input::parse_input_lines::<String>(input)
-            .unwrap()
-            .first()
-            .expect("Unable to read input")
-            .clone();
+        let base_key = secret_key(input)?;
         let difficulty = 6;
-        let k = self.find_hash_integer::<usize>(&base_key, difficulty);
-        Ok(vec![k.to_string()])
+        let k = self.find_hash_integer(&base_key, difficulty)?;
+        Ok(k.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{cancel, error::SolutionError};
 
     #[test]
     fn test_santa_hashes() {
-        let validations = vec![("pqrstuv", 5, 1048970)];
+        let validations = vec![("abcdef", 4, 31556), ("pqrstuv", 5, 1048970)];
 
-        let day = Day4::new();
+        let day = Day4::with_miner(Miner::new().with_checkpoint_dir(None));
         for (input, difficulty, expected_result) in validations.into_iter() {
-            let k = day.find_hash_integer::<usize>(&input.into(), difficulty);
+            let k = day.find_hash_integer(input, difficulty).unwrap();
 
             assert_eq!(k, expected_result);
         }
     }
+
+    #[test]
+    fn test_cancelled_search() {
+        let token = cancel::CancelToken::new();
+        token.cancel();
+
+        let day = Day4::with_miner(Miner::new().with_checkpoint_dir(None));
+        let result = token.scope(|| day.part2("pqrstuv"));
+        assert!(matches!(result, Err(SolutionError::Cancelled)));
+    }
 }
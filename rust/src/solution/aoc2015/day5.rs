@@ -1,6 +1,6 @@
 use crate::{
     input,
-    solution::{PartResult, Solution},
+    solution::{PartResult, Solution, registry::register_solution},
 };
 use itertools::Itertools;
 
@@ -12,6 +12,8 @@ impl Day5 {
         Self {}
     }
 }
+
+register_solution!(Day5, year: 2015, day: 5, title: "Doesn't He Have Intern-Elves For This?");
 fn count_vowels(input: &str) -> usize {
     let vowels = ['a', 'e', 'i', 'o', 'u'];
     input.chars().filter(|c| vowels.contains(c)).count()
@@ -49,19 +51,19 @@ fn is_super_nice(input: &str) -> bool {
 
 impl Solution for Day5 {
     fn part1(&self, input: &str) -> PartResult {
-        let lines = input::parse_input_lines::<String>(input).unwrap();
+        let lines = input::parse_input_lines::<String>(input)?;
 
         let nice_words: usize = lines.iter().filter(|line| is_nice(line)).count();
 
-        Ok(vec![nice_words.to_string()])
+        Ok(nice_words.into())
     }
 
     fn part2(&self, input: &str) -> PartResult {
-        let lines = input::parse_input_lines::<String>(input).unwrap();
+        let lines = input::parse_input_lines::<String>(input)?;
 
         let super_nice_words: usize = lines.iter().filter(|line| is_super_nice(line)).count();
 
-        Ok(vec![super_nice_words.to_string()])
+        Ok(super_nice_words.into())
     }
 }
 
@@ -1,12 +1,14 @@
-use anyhow::Result;
-use itertools::Itertools;
 use na::DMatrix;
 use regex::Regex;
 use std::{ops::Add, str::FromStr};
 
 use crate::{
-    input,
-    solution::{PartResult, Solution},
+    input::{
+        self,
+        diagnostic::{self, Diagnostic},
+        error::InputError,
+    },
+    solution::{PartResult, TypedSolution, error, registry::register_solution},
 };
 
 #[derive(Debug, Clone)]
@@ -18,6 +20,8 @@ impl Day6 {
     }
 }
 
+register_solution!(Day6, year: 2015, day: 6, title: "Probably a Fire Hazard");
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Position(usize, usize);
 
@@ -28,15 +32,12 @@ impl From<(usize, usize)> for Position {
 }
 
 impl FromStr for Position {
-    type Err = std::num::ParseIntError;
+    type Err = InputError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let [x, y]: [usize; 2] = s
-            .split(',')
-            .map(|p| p.parse().unwrap())
-            .collect_vec()
-            .try_into()
-            .unwrap();
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let [x, y]: [usize; 2] = input::split_line(s, ',')?.try_into().map_err(|_| {
+            Diagnostic::expected(s, diagnostic::columns(s, 0..s.len()), "`<x>,<y>`")
+        })?;
 
         Ok(Self(x, y))
     }
@@ -63,34 +64,45 @@ pub struct Cmd {
 }
 
 impl FromStr for Action {
-    type Err = regex::Error;
+    type Err = InputError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
         match s {
             "toggle" => Ok(Self::Toggle),
             "turn on" => Ok(Self::On),
             "turn off" => Ok(Self::Off),
-            x => panic!("Unknown instruction {}", x),
+            _ => Err(Diagnostic::expected(
+                s,
+                diagnostic::columns(s, 0..s.len()),
+                "`toggle`, `turn on` or `turn off`",
+            ))?,
         }
     }
 }
 
 impl FromStr for Cmd {
-    type Err = regex::Error;
+    type Err = InputError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
         let re = Regex::new(r"^(?P<action>.+) (?P<pos1>\d+,\d+) .+ (?P<pos2>\d+,\d+)").unwrap();
-        let captures = re.captures(s).unwrap();
-
-        let pos1: Position = Position::from_str(captures.name("pos1").unwrap().as_str()).unwrap();
-        let pos2: Position = Position::from_str(captures.name("pos2").unwrap().as_str()).unwrap();
+        let captures = re.captures(s).ok_or_else(|| {
+            Diagnostic::expected(
+                s,
+                diagnostic::columns(s, 0..s.len()),
+                "`<action> <x>,<y> through <x>,<y>`",
+            )
+        })?;
 
-        let action: Action = match &captures["action"] {
-            "toggle" => Action::Toggle,
-            "turn on" => Action::On,
-            "turn off" => Action::Off,
-            x => panic!("Unknown instruction {}", x),
+        let parse_capture = |name: &str| {
+            let capture = captures.name(name).unwrap();
+            (capture.as_str(), capture.start())
         };
+        let (pos1, offset) = parse_capture("pos1");
+        let pos1: Position = Position::from_str(pos1).map_err(|e| e.within(s, offset))?;
+        let (pos2, offset) = parse_capture("pos2");
+        let pos2: Position = Position::from_str(pos2).map_err(|e| e.within(s, offset))?;
+        let (action, offset) = parse_capture("action");
+        let action: Action = Action::from_str(action).map_err(|e| e.within(s, offset))?;
 
         Ok(Self {
             action,
@@ -174,39 +186,36 @@ impl Grid {
     }
 }
 
-impl Solution for Day6 {
-    fn part1(&self, input: &str) -> PartResult {
-        let lines = input::parse_input_lines::<String>(input).unwrap();
+impl TypedSolution for Day6 {
+    type Parsed = Vec<Cmd>;
+
+    fn parse(input: &str) -> error::Result<Self::Parsed> {
+        let cmds = input::parse_input_lines_with(input, |line| match line.is_empty() {
+            true => Ok(None),
+            false => Cmd::from_str(line).map(Some),
+        })?;
 
+        Ok(cmds.into_iter().flatten().collect())
+    }
+
+    fn part1(&self, cmds: &Self::Parsed) -> PartResult {
         let mut grid = Grid::new((1000, 1000));
 
-        lines
-            .iter()
-            .filter(|line| !line.is_empty())
-            .map(|line| {
-                let cmd = Cmd::from_str(line).unwrap();
-                grid.translate_cmd_1(&cmd);
-            })
-            .collect_vec();
+        for cmd in cmds {
+            grid.translate_cmd_1(cmd);
+        }
 
-        Ok(vec![grid.sum().to_string()])
+        Ok(grid.sum().into())
     }
 
-    fn part2(&self, input: &str) -> PartResult {
-        let lines = input::parse_input_lines::<String>(input).unwrap();
-
+    fn part2(&self, cmds: &Self::Parsed) -> PartResult {
         let mut grid = Grid::new((1000, 1000));
 
-        lines
-            .iter()
-            .filter(|line| !line.is_empty())
-            .map(|line| {
-                let cmd = Cmd::from_str(line).unwrap();
-                grid.translate_cmd_2(&cmd);
-            })
-            .collect_vec();
+        for cmd in cmds {
+            grid.translate_cmd_2(cmd);
+        }
 
-        Ok(vec![grid.sum().to_string()])
+        Ok(grid.sum().into())
     }
 }
 
@@ -249,6 +258,48 @@ mod tests {
             assert_eq!(result, expected_result);
         }
     }
+
+    #[test]
+    fn malformed_instructions() {
+        let validations = vec![
+            (
+                "turn on 1,2 to",
+                1..15,
+                "`<action> <x>,<y> through <x>,<y>`",
+            ),
+            (
+                "switch 1,2 through 3,4",
+                1..7,
+                "`toggle`, `turn on` or `turn off`",
+            ),
+        ];
+        let positions = vec![("1,2", Ok(Position(1, 2))), ("1,2,3", Err(1..6))];
+        for (input, expected_result) in positions.into_iter() {
+            let result =
+                Position::from_str(input).map_err(|e| e.diagnostic().unwrap().columns.clone());
+            assert_eq!(result, expected_result);
+        }
+        let error = Cmd::from_str("toggle 1,2 through 3,99999999999999999999").unwrap_err();
+        assert_eq!(error.diagnostic().unwrap().columns, 22..42);
+
+        for (input, expected_columns, expected) in validations.into_iter() {
+            let error = Cmd::from_str(input).unwrap_err();
+            let diagnostic = error.diagnostic().unwrap();
+            assert_eq!(diagnostic.columns, expected_columns);
+            assert_eq!(diagnostic.expected.as_deref(), Some(expected));
+        }
+
+        let error = <Day6 as TypedSolution>::parse(
+            "toggle 0,0 through 1,1
+
+flip 0,0 through 1,1",
+        );
+        match error {
+            Err(error::SolutionError::Input(e)) => assert_eq!(e.diagnostic().unwrap().line, 3),
+            r => panic!("Should have been a parsing error, got {r:?}"),
+        }
+    }
+
     #[test]
     fn grid_set_on() {
         let shape = (3, 3);
@@ -1,7 +1,7 @@
 use std::{
-    cell::RefCell,
     collections::{HashMap, HashSet},
-    fmt::Debug,
+    fmt::{Debug, Display},
+    sync::LazyLock,
 };
 
 use regex::Regex;
@@ -9,17 +9,22 @@ use std::str::FromStr;
 use strum_macros::EnumString;
 
 use crate::{
-    input,
-    solution::{PartResult, Solution},
+    graph::{DiGraph, dot::Dot},
+    input::{
+        self,
+        diagnostic::{self, Diagnostic},
+        error::InputError,
+    },
+    solution::{PartResult, TypedSolution, error::Result, registry::register_solution},
 };
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum Input {
     Value(u16),
     Signal(String),
 }
 
-#[derive(Debug, PartialEq, Eq, EnumString)]
+#[derive(Debug, Clone, PartialEq, Eq, EnumString)]
 enum Operation {
     #[strum(disabled)]
     Wire,
@@ -35,25 +40,44 @@ enum Operation {
     RShift(u16),
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Connection {
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wire => write!(f, "WIRE"),
+            Self::Not => write!(f, "NOT"),
+            Self::And => write!(f, "AND"),
+            Self::Or => write!(f, "OR"),
+            Self::LShift(count) => write!(f, "LSHIFT {}", count),
+            Self::RShift(count) => write!(f, "RSHIFT {}", count),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connection {
     operation: Operation,
     inputs: HashSet<Input>,
     output: String,
 }
-impl Connection {}
+
+static CONNECTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?P<in1>[a-z0-9]+)?\s?(?P<action>[A-Z]+)?\s?(?P<in2>[a-z0-9]+)? -> (?P<output>\D+)",
+    )
+    .unwrap()
+});
 
 impl FromStr for Connection {
-    type Err = regex::Error;
+    type Err = InputError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = Regex::new(
-            r"^(?P<in1>[a-z0-9]+)?\s?(?P<action>[A-Z]+)?\s?(?P<in2>[a-z0-9]+)? -> (?P<output>\D+)",
-        )
-        .unwrap();
-        let captures = re
-            .captures(s)
-            .expect("Unable to parse connection from input {s}. Wrong input format.");
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let captures = CONNECTION_RE.captures(s).ok_or_else(|| {
+            Diagnostic::expected(
+                s,
+                diagnostic::columns(s, 0..s.len()),
+                "`[<input>] [<OPERATION>] [<input>] -> <wire>`",
+            )
+        })?;
 
         let mut inputs: HashMap<&str, Input> = ["in1", "in2"]
             .iter()
@@ -68,29 +92,50 @@ impl FromStr for Connection {
 
         let operation = match captures.name("action") {
             Some(action) => {
-                let op = Operation::from_str(action.as_str()).unwrap();
+                let op = Operation::from_str(action.as_str()).map_err(|e| {
+                    InputError::parsing(s, diagnostic::columns(s, action.range()), e)
+                })?;
                 match op {
                     Operation::LShift(_) | Operation::RShift(_) => {
-                        let in2 = inputs.remove("in2").unwrap();
-                        match (&op, in2) {
-                            (Operation::LShift(_), Input::Value(val)) => Operation::LShift(val),
-                            (Operation::RShift(_), Input::Value(val)) => Operation::RShift(val),
-                            (Operation::RShift(_) | Operation::LShift(_), Input::Signal(_)) => {
-                                panic!(
-                                    "Input 2 must be parseable as u16 if action is LShift or RShift."
-                                )
-                            }
-                            _ => op,
+                        // Signals are 16 bits wide, so larger shifts would overflow
+                        let shift_count = match captures.name("in2") {
+                            Some(in2) => match in2.as_str().parse::<u16>() {
+                                Ok(count) if count < u16::BITS as u16 => Ok(count),
+                                _ => Err(in2.range()),
+                            },
+                            None => Err(action.end()..action.end()),
+                        }
+                        .map_err(|range| {
+                            Diagnostic::expected(
+                                s,
+                                diagnostic::columns(s, range),
+                                "a shift count below 16",
+                            )
+                        })?;
+                        inputs.remove("in2");
+                        match op {
+                            Operation::LShift(_) => Operation::LShift(shift_count),
+                            _ => Operation::RShift(shift_count),
                         }
                     }
                     op => op,
                 }
             }
-            None => match inputs.get("in1").unwrap() {
-                Input::Value(_) => Operation::Wire,
-                Input::Signal(_) => Operation::Wire,
-            },
+            None => Operation::Wire,
+        };
+
+        let (expected_inputs, expected) = match operation {
+            Operation::And | Operation::Or => (2, "two inputs"),
+            _ => (1, "a single input"),
         };
+        if inputs.len() != expected_inputs {
+            let arrow = s.find(" -> ").unwrap_or_default();
+            Err(Diagnostic::expected(
+                s,
+                diagnostic::columns(s, 0..arrow),
+                expected,
+            ))?
+        }
 
         let output = captures.name("output").unwrap().as_str().to_string();
         Ok(Connection {
@@ -141,24 +186,26 @@ impl Circuit {
         }
     }
 
-    fn process_wire(&mut self, connection: &Connection) {
-        let input = connection.inputs.iter().next().unwrap();
-        match self.get_input_value(input) {
-            Some(value) => {
-                self.signals.insert(connection.output.clone(), value);
-            }
-            None => panic!("Input {:?} is not defined", input),
-        }
+    /// Value of the only input of a wire, a NOT gate or a shift
+    fn get_single_input_value(&self, connection: &Connection) -> Result<u16> {
+        let input =
+            connection.inputs.iter().next().ok_or_else(|| {
+                anyhow::anyhow!("Connection to {} has no input", connection.output)
+            })?;
+        self.get_input_value(input)
+            .ok_or_else(|| anyhow::anyhow!("Input {:?} is not defined", input).into())
     }
 
-    fn process_not(&mut self, connection: &Connection) {
-        let input = connection.inputs.iter().next().unwrap();
-        match self.get_input_value(input) {
-            Some(value) => {
-                self.signals.insert(connection.output.clone(), !value);
-            }
-            None => panic!("Input {:?} is not defined", input),
-        }
+    fn process_wire(&mut self, connection: &Connection) -> Result<()> {
+        let value = self.get_single_input_value(connection)?;
+        self.signals.insert(connection.output.clone(), value);
+        Ok(())
+    }
+
+    fn process_not(&mut self, connection: &Connection) -> Result<()> {
+        let value = self.get_single_input_value(connection)?;
+        self.signals.insert(connection.output.clone(), !value);
+        Ok(())
     }
     fn process_and(&mut self, connection: &Connection) {
         if self.get_connection_missing_inputs(connection).is_some() {
@@ -181,36 +228,37 @@ impl Circuit {
         self.signals.insert(connection.output.clone(), value);
     }
 
-    fn process_shift(&mut self, connection: &Connection) {
+    fn process_shift(&mut self, connection: &Connection) -> Result<()> {
         if self.get_connection_missing_inputs(connection).is_some() {
-            return;
+            return Ok(());
         }
-        let input = connection.inputs.iter().next().unwrap();
-        let input_value = self.get_input_value(input).unwrap();
+        let input_value = self.get_single_input_value(connection)?;
         let value = match connection.operation {
             Operation::LShift(shift_count) => input_value << shift_count,
             Operation::RShift(shift_count) => input_value >> shift_count,
-            _ => panic!(
+            _ => Err(anyhow::anyhow!(
                 "Unable to process shift operation. Received {:?}",
                 connection.operation
-            ),
+            ))?,
         };
 
         self.signals.insert(connection.output.clone(), value);
+        Ok(())
     }
 
-    fn process_connection(&mut self, connection: Connection) {
+    fn process_connection(&mut self, connection: Connection) -> Result<()> {
         match connection.operation {
-            Operation::Wire => self.process_wire(&connection),
-            Operation::Not => self.process_not(&connection),
+            Operation::Wire => self.process_wire(&connection)?,
+            Operation::Not => self.process_not(&connection)?,
             Operation::And => self.process_and(&connection),
             Operation::Or => self.process_or(&connection),
-            Operation::LShift(_) | Operation::RShift(_) => self.process_shift(&connection),
+            Operation::LShift(_) | Operation::RShift(_) => self.process_shift(&connection)?,
         };
-        self.connections.push(connection)
+        self.connections.push(connection);
+        Ok(())
     }
 
-    fn process_remaining_connections(&mut self) {
+    fn process_remaining_connections(&mut self) -> Result<()> {
         let mut i = 0;
         while i < self.remaining_connections.len() {
             if self
@@ -222,22 +270,23 @@ impl Circuit {
             }
             let conn = self.remaining_connections.remove(i);
 
-            self.process_connection(conn);
+            self.process_connection(conn)?;
             i = 0
         }
+        Ok(())
     }
 
-    fn add_connection(&mut self, connection: Connection) {
+    fn add_connection(&mut self, connection: Connection) -> Result<()> {
         let missing_input_names = self.get_connection_missing_inputs(&connection);
         match missing_input_names {
-            None => self.process_connection(connection),
+            None => self.process_connection(connection)?,
             Some(_missing_names) => {
                 self.remaining_connections.push(connection);
-                return;
+                return Ok(());
             }
         }
 
-        self.process_remaining_connections();
+        self.process_remaining_connections()
     }
 }
 
@@ -247,69 +296,144 @@ impl Default for Circuit {
     }
 }
 
-#[derive(Debug)]
-pub struct Day7 {
-    circuit: RefCell<Circuit>,
-    input_lines: RefCell<Vec<String>>,
+impl Circuit {
+    /// Connects every wire, with the `overrides` signals taking precedence over their connections
+    fn from_connections(
+        connections: &[Connection],
+        overrides: HashMap<String, u16>,
+    ) -> Result<Self> {
+        let mut circuit = Circuit::new(overrides, Vec::new());
+        for conn in connections {
+            if circuit.signals.contains_key(&conn.output) {
+                continue;
+            }
+            circuit.add_connection(conn.clone())?;
+        }
+        Ok(circuit)
+    }
+
+    fn get_signal(&self, name: &str) -> Result<u16> {
+        self.signals
+            .get(name)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Signal {name} is not connected").into())
+    }
 }
 
-impl Day7 {
-    pub fn new() -> Self {
-        Self {
-            circuit: RefCell::new(Circuit::default()),
-            input_lines: RefCell::new(vec![]),
+/// Element of the circuit as drawn by [`Day7`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CircuitNode {
+    Wire(String),
+    /// Constant input of the connection at that index
+    Value(usize, u16),
+    /// Gate of the connection at that index
+    Gate(usize),
+}
+
+/// Network of wires and gates, with edges following the signals
+fn circuit_graph(connections: &[Connection]) -> DiGraph<CircuitNode, ()> {
+    let mut graph = DiGraph::new();
+    for (i, connection) in connections.iter().enumerate() {
+        let output = graph.add_vertex(CircuitNode::Wire(connection.output.clone()));
+        // Plain wires connect their input straight to their output
+        let target = match connection.operation {
+            Operation::Wire => output,
+            _ => {
+                let gate = graph.add_vertex(CircuitNode::Gate(i));
+                graph.add_edge_by_id(gate, output, ());
+                gate
+            }
+        };
+
+        let mut inputs: Vec<_> = connection.inputs.iter().collect();
+        inputs.sort();
+        for input in inputs {
+            let source = match input {
+                Input::Value(value) => CircuitNode::Value(i, *value),
+                Input::Signal(name) => CircuitNode::Wire(name.clone()),
+            };
+            let source = graph.add_vertex(source);
+            graph.add_edge_by_id(source, target, ());
         }
     }
-    fn with_input(&self, input: &str) -> &Self {
-        let mut input_lines = self.input_lines.borrow_mut();
-        *input_lines = input::parse_input_lines(input).unwrap();
-        self
+    graph
+}
+
+#[derive(Debug)]
+pub struct Day7 {}
+
+impl Day7 {
+    pub fn new() -> Self {
+        Self {}
     }
 }
 
-impl Solution for Day7 {
-    fn part1(&self, input: &str) -> PartResult {
-        self.with_input(input);
-        let mut circuit = self.circuit.borrow_mut();
-        for line in self.input_lines.borrow().iter() {
-            let conn = Connection::from_str(line).unwrap();
-            circuit.add_connection(conn);
-        }
+register_solution!(Day7, year: 2015, day: 7, title: "Some Assembly Required");
+
+impl TypedSolution for Day7 {
+    type Parsed = Vec<Connection>;
 
-        Ok(vec![circuit.signals.get("a").unwrap().to_string()])
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input::parse_input_lines_with(input, Connection::from_str)?)
     }
 
-    fn part2(&self, input: &str) -> PartResult {
-        self.with_input(input.as_ref());
-        let mut circuit = self.circuit.borrow_mut();
-        let prev_value = circuit.signals.get("a").unwrap().to_owned();
-        // Clear all signal values and redefine the value for b
-        circuit.signals.clear();
-        circuit.signals.insert("b".to_string(), prev_value);
+    fn part1(&self, connections: &Self::Parsed) -> PartResult {
+        let circuit = Circuit::from_connections(connections, HashMap::new())?;
+
+        Ok(circuit.get_signal("a")?.into())
+    }
 
-        // ensure there are no remaining connections
-        circuit.remaining_connections.clear();
+    fn part2(&self, connections: &Self::Parsed) -> PartResult {
+        let prev_value = Circuit::from_connections(connections, HashMap::new())?.get_signal("a")?;
 
-        // Move all circuit connections to remaining_connections excluding signal b
-        let mut remaining_connections = circuit
-            .connections
-            .drain(..)
-            .filter(|conn| conn.output != *"b")
-            .collect::<Vec<_>>();
-        circuit
-            .remaining_connections
-            .append(&mut remaining_connections);
+        // Rebuild the circuit with the value of a redefining signal b
+        let overrides = HashMap::from([("b".to_string(), prev_value)]);
+        let circuit = Circuit::from_connections(connections, overrides)?;
 
-        circuit.process_remaining_connections();
+        Ok(circuit.get_signal("a")?.into())
+    }
 
-        let result = circuit.signals.get("a").unwrap().to_owned();
-        Ok(vec![result.to_string()])
+    fn to_dot(&self, connections: &Self::Parsed) -> Result<Option<String>> {
+        let circuit = Circuit::from_connections(connections, HashMap::new())?;
+        let graph = circuit_graph(connections);
+
+        let dot = Dot::new(&graph, "day7")
+            .with_attributes(vec![("rankdir", "LR".into())])
+            .with_vertex_attributes(|_, node| match node {
+                // Wires are labeled along with the signal they carry
+                CircuitNode::Wire(name) => match circuit.signals.get(name) {
+                    Some(signal) => vec![("label", format!("{}\n{}", name, signal))],
+                    None => vec![("label", name.clone())],
+                },
+                CircuitNode::Value(_, value) => {
+                    vec![("label", value.to_string()), ("shape", "plaintext".into())]
+                }
+                CircuitNode::Gate(i) => vec![
+                    ("label", connections[*i].operation.to_string()),
+                    ("shape", "box".into()),
+                ],
+            });
+        Ok(Some(dot.to_string()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{Solution, answer::Answer};
+
+    #[test]
+    fn test_invalid_connections() {
+        let mut circuit = Circuit::default();
+
+        let undefined_input = Connection::from_str("x -> y").unwrap();
+        assert!(circuit.process_wire(&undefined_input).is_err());
+        assert!(circuit.process_not(&undefined_input).is_err());
+
+        let not_a_shift = Connection::from_str("NOT 3 -> y").unwrap();
+        assert!(circuit.process_shift(&not_a_shift).is_err());
+        assert!(circuit.signals.is_empty());
+    }
 
     #[test]
     fn test_wire() {
@@ -318,7 +442,7 @@ mod tests {
         let connections = ["42 -> x", "x -> y"].map(|input| Connection::from_str(input).unwrap());
 
         for conn in connections.into_iter() {
-            circuit.add_connection(conn);
+            circuit.add_connection(conn).unwrap();
         }
         assert_eq!(*circuit.signals.get("x").unwrap(), 42u16);
         assert_eq!(*circuit.signals.get("y").unwrap(), 42u16);
@@ -331,7 +455,7 @@ mod tests {
             ["42 -> x", "NOT x -> y"].map(|input| Connection::from_str(input).unwrap());
 
         for conn in connections.into_iter() {
-            circuit.add_connection(conn);
+            circuit.add_connection(conn).unwrap();
         }
         assert_eq!(*circuit.signals.get("x").unwrap(), 42u16);
         assert_eq!(*circuit.signals.get("y").unwrap(), u16::MAX - 42);
@@ -345,7 +469,7 @@ mod tests {
             .map(|input| Connection::from_str(input).unwrap());
 
         for conn in connections.into_iter() {
-            circuit.add_connection(conn);
+            circuit.add_connection(conn).unwrap();
         }
         assert_eq!(*circuit.signals.get("x").unwrap(), 42u16);
         assert_eq!(*circuit.signals.get("y").unwrap(), 11u16);
@@ -371,9 +495,8 @@ mod tests {
         .map(|input| Connection::from_str(input).unwrap());
 
         for conn in connections.into_iter() {
-            circuit.add_connection(conn);
+            circuit.add_connection(conn).unwrap();
         }
-        dbg!(&circuit.signals);
         assert_eq!(*circuit.signals.get("nz").unwrap(), !72);
         assert_eq!(*circuit.signals.get("z").unwrap(), 72);
         assert_eq!(*circuit.signals.get("d").unwrap(), 72);
@@ -460,4 +583,70 @@ mod tests {
             assert_eq!(connection, expected_result);
         }
     }
+
+    #[test]
+    fn test_malformed_connections() {
+        let validations = vec![
+            ("x XOR y -> z", 3..6, "XOR"),
+            ("x AND y", 1..8, "x AND y"),
+            ("x LSHIFT 16 -> y", 10..12, "16"),
+            ("x RSHIFT -> y", 9..9, ""),
+        ];
+
+        for (input, expected_columns, expected_found) in validations.into_iter() {
+            let error = Connection::from_str(input).unwrap_err();
+            let diagnostic = error.diagnostic().unwrap();
+            assert_eq!(diagnostic.columns, expected_columns);
+            assert_eq!(diagnostic.found, expected_found);
+        }
+    }
+
+    #[test]
+    fn test_dot() {
+        let input = "123 -> x\n456 -> y\nx AND y -> d\nx LSHIFT 2 -> f\nNOT y -> i\nd -> a";
+        let dot = Solution::to_dot(&Day7::new(), input).unwrap().unwrap();
+
+        assert!(dot.starts_with("digraph \"day7\" {\n    rankdir=\"LR\";\n"));
+        for label in [
+            "label=\"x\\n123\"",
+            "label=\"d\\n72\"",
+            "label=\"a\\n72\"",
+            "label=\"AND\", shape=\"box\"",
+            "label=\"LSHIFT 2\", shape=\"box\"",
+            "label=\"456\", shape=\"plaintext\"",
+        ] {
+            assert!(dot.contains(label), "{label} not in {dot}");
+        }
+        // Constants and plain wires, then each gate along with its inputs
+        assert_eq!(dot.matches(" -> ").count(), 2 + 3 + 2 + 2 + 1);
+    }
+
+    #[test]
+    fn test_parts_are_independent() {
+        let input = "123 -> x\nx AND b -> a\n456 -> b\n";
+        let day = Day7::new();
+
+        // Part 2 no longer depends on part 1 having been solved first
+        assert_eq!(Solution::part2(&day, input).unwrap(), Answer::Integer(72));
+        assert_eq!(Solution::part1(&day, input).unwrap(), Answer::Integer(72));
+
+        let parsed = <Day7 as TypedSolution>::parse(input).unwrap();
+        assert_eq!(
+            TypedSolution::part1(&day, &parsed).unwrap(),
+            Answer::Integer(72)
+        );
+        assert_eq!(
+            TypedSolution::part2(&day, &parsed).unwrap(),
+            Answer::Integer(72)
+        );
+    }
+
+    #[test]
+    fn test_part_2_overrides_b() {
+        let input = "3 -> b\nb LSHIFT 1 -> a";
+        let day = Day7::new();
+
+        assert_eq!(Solution::part1(&day, input).unwrap(), Answer::Integer(6));
+        assert_eq!(Solution::part2(&day, input).unwrap(), Answer::Integer(12));
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     input,
-    solution::{PartResult, Solution},
+    solution::{PartResult, Solution, registry::register_solution},
 };
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -92,15 +92,17 @@ impl Day8 {
     }
 }
 
+register_solution!(Day8, year: 2015, day: 8, title: "Matchsticks");
+
 impl Solution for Day8 {
     fn part1(&self, input: &str) -> PartResult {
         let counts = Day8::part_1(input);
-        Ok(vec![counts.to_string()])
+        Ok(counts.into())
     }
 
     fn part2(&self, input: &str) -> PartResult {
         let counts = Day8::part_2(input);
-        Ok(vec![counts.to_string()])
+        Ok(counts.into())
     }
 }
 
@@ -144,13 +146,13 @@ mod tests {
 
     #[test]
     fn part_2_example_file() {
-        let test_files_setups = [("../inputs/2015/day8_example.txt", 19)];
+        let test_files_setups = [("../inputs/2015/examples/day8_1.txt", 19)];
 
-        for (input_file_path, _expected_result) in test_files_setups.iter() {
+        for (input_file_path, expected_result) in test_files_setups.iter() {
             let file_content = fs::read_to_string(input_file_path).unwrap();
 
             let part_2 = Day8::part_2(&file_content);
-            dbg!(part_2);
+            assert_eq!(part_2, *expected_result);
         }
     }
 
@@ -192,7 +194,7 @@ mod tests {
         let test_files_setups = [
             ("../inputs/2015/day8_jkpr.txt", 1350),
             ("../inputs/2015/day8_jocelyn_stericher.txt", 1371),
-            ("../inputs/2015/day8_example.txt", 12),
+            ("../inputs/2015/examples/day8_1.txt", 12),
             ("../inputs/2015/day8.txt", 1342),
         ];
 
@@ -1,100 +1,77 @@
-#![allow(dead_code)]
-
 use itertools::Itertools;
 
+use crate::graph::{UnGraph, dot::Dot};
 use crate::input;
 use crate::solution::error::Result;
-use crate::solution::{PartResult, Solution};
-use hex;
-use sha2::{Digest, Sha256};
-
-use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
-use std::ops::{Deref, DerefMut};
+use crate::solution::{PartResult, TypedSolution, cancel, registry::register_solution};
 
 #[derive(Debug)]
-pub struct Day9 {
-    input_hash: RefCell<Option<String>>,
-    graph: RefCell<Option<AdjacencyList>>,
-}
+pub struct Day9 {}
 
 impl Day9 {
     pub fn new() -> Self {
-        Self {
-            input_hash: RefCell::new(None),
-            graph: RefCell::new(None),
-        }
+        Self {}
     }
+}
 
-    fn hash_input(&self, input: &str) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(input);
-        let result = hasher.finalize();
-        hex::encode(result)
-    }
-    fn build_graph(&self, input: &str) -> Result<AdjacencyList> {
-        let input_hash = self.hash_input(input);
-        if let Some(current_hash) = self.input_hash.borrow().clone() {
-            if input_hash == current_hash {
-                if let Some(g) = self.graph.borrow_mut().clone() {
-                    debug!("Returning graph from cache");
-                    return Ok(g);
-                }
-            }
-        }
+register_solution!(Day9, year: 2015, day: 9, title: "All in a Single Night");
 
+impl TypedSolution for Day9 {
+    type Parsed = Cities;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
         debug!("Preparing a new graph for traversal");
-        let lines: Vec<String> = input::parse_input_lines(input).unwrap();
+        let edges = input::parse_input_lines_with(input, parsing::try_parse_edge)?;
 
-        let mut graph = AdjacencyList::default();
-        for line in lines.iter() {
-            let edge = parsing::try_parse_edge(line).unwrap();
-            graph.try_add_edge(edge).unwrap();
+        let mut graph = Cities::new();
+        for edge in edges {
+            graph.add_edge(edge.left, edge.right, edge.value);
         }
-
-        self.graph.replace(Some(graph.clone()));
-        self.input_hash.replace(Some(input_hash));
         Ok(graph)
     }
-    fn search_full_paths(&self, input: &str) -> Result<Vec<(Vec<Vertex>, u32)>> {
-        let graph = self.build_graph(input)?;
-        let paths = search_full_paths_on_graph(&graph);
-        Ok(paths)
-    }
-}
 
-impl Solution for Day9 {
-    fn part1(&self, input: &str) -> PartResult {
-        let paths = self.search_full_paths(input).unwrap();
-        let sorted_paths = paths.into_iter().sorted_by_key(|p| p.1).collect_vec();
-        if let Some(shortest_path) = sorted_paths.first() {
-            let ppath = shortest_path.0.iter().map(|v| v.0.clone()).join(" -> ");
-            debug!("[{}] {}", shortest_path.1, ppath);
-            Ok(vec![format!("{}", shortest_path.1)])
-        } else {
-            Ok(vec!["Incomplete".to_string()])
-        }
+    fn part1(&self, graph: &Self::Parsed) -> PartResult {
+        let route = best_route(graph, Objective::Shortest, RouteKind::Open)?;
+        Ok(route.map(|route| route.distance).into())
     }
 
-    fn part2(&self, input: &str) -> PartResult {
-        let paths = self.search_full_paths(input).unwrap();
-        let sorted_paths = paths.into_iter().sorted_by_key(|p| p.1).collect_vec();
+    fn part2(&self, graph: &Self::Parsed) -> PartResult {
+        let route = best_route(graph, Objective::Longest, RouteKind::Open)?;
+        Ok(route.map(|route| route.distance).into())
+    }
 
-        if let Some(longest_path) = sorted_paths.last() {
-            let ppath = longest_path.0.iter().map(|v| v.0.clone()).join(" -> ");
-            debug!("[{}] {}", longest_path.1, ppath);
-            Ok(vec![format!("{}", longest_path.1)])
-        } else {
-            Ok(vec!["Incomplete".to_string()])
+    fn to_dot(&self, graph: &Self::Parsed) -> Result<Option<String>> {
+        let mut dot = Dot::new(graph, "day9")
+            .with_vertex_attributes(|_, city| vec![("label", city.to_string())])
+            .with_edge_attributes(|_, _, distance| {
+                vec![("label", distance.to_string()), ("color", "gray".into())]
+            });
+
+        let mut legend = Vec::new();
+        for (name, objective, color) in [
+            ("Shortest", Objective::Shortest, "blue"),
+            ("Longest", Objective::Longest, "red"),
+        ] {
+            if let Some(route) = best_route(graph, objective, RouteKind::Open)? {
+                let ids = route
+                    .vertexes
+                    .iter()
+                    .filter_map(|v| graph.id(v))
+                    .collect_vec();
+                let attributes = vec![("color", color.into()), ("penwidth", "3".into())];
+                dot = dot.with_path(&ids, attributes);
+                legend.push(format!("{} route: {} ({})", name, route.distance, color));
+            }
         }
+        let dot = dot.with_attributes(vec![("label", legend.join("\n"))]);
+        Ok(Some(dot.to_string()))
     }
 }
 
-type VertexId = u8;
 type EdgeValue = u16;
 
-#[derive(Clone, Debug, Hash, Eq, PartialEq, PartialOrd)]
-struct Vertex(String);
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Vertex(String);
 
 impl Vertex {
     pub fn new(name: &str) -> Self {
@@ -102,8 +79,14 @@ impl Vertex {
     }
 }
 
+impl std::fmt::Display for Vertex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, PartialOrd)]
-struct Edge {
+pub struct Edge {
     left: Vertex,
     right: Vertex,
     value: EdgeValue,
@@ -119,170 +102,205 @@ impl Edge {
     }
 }
 
-#[derive(Clone, Debug, Default)]
-struct AdjacencyList {
-    inner: HashMap<Vertex, HashMap<Vertex, EdgeValue>>,
-    vertexes: HashSet<Vertex>,
-}
-
-impl AdjacencyList {
-    pub fn try_add_edge(&mut self, edge: Edge) -> Result<()> {
-        self.vertexes.insert(edge.left.clone());
-        self.vertexes.insert(edge.right.clone());
-
-        match self.get_mut(&edge.left) {
-            Some(connections) => {
-                connections.insert(edge.right.clone(), edge.value);
-            }
-            None => {
-                self.insert(
-                    edge.left.clone(),
-                    HashMap::from([(edge.right.clone(), edge.value)]),
-                );
-            }
-        };
-        match self.get_mut(&edge.right) {
-            Some(connections) => {
-                connections.insert(edge.left.clone(), edge.value);
-            }
-            None => {
-                self.insert(edge.right, HashMap::from([(edge.left, edge.value)]));
-            }
-        };
-        Ok(())
-    }
-}
-
-impl Deref for AdjacencyList {
-    type Target = HashMap<Vertex, HashMap<Vertex, EdgeValue>>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.inner
-    }
-}
-
-impl DerefMut for AdjacencyList {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.inner
-    }
-}
+/// Cities joined by the distances between them
+pub type Cities = UnGraph<Vertex, EdgeValue>;
 
 mod parsing {
     use nom::branch::alt;
+    use nom::error::context;
     use nom::sequence::separated_pair;
     use nom::{
-        Parser,
+        IResult, Parser,
         bytes::complete::tag,
         character::complete::{self, alphanumeric1, space0, space1},
         sequence::delimited,
     };
 
-    use crate::error::Result;
-    use crate::input::error::InputError;
+    use crate::input::diagnostic::{Diagnostic, NomError};
+    use crate::input::error::Result;
 
     use super::Edge;
-    fn vertex_name(input: &str) -> nom::IResult<&str, &str> {
-        alphanumeric1(input)
+    fn vertex_name(input: &str) -> IResult<&str, &str, NomError<'_>> {
+        context("a location name", alphanumeric1).parse(input)
     }
 
-    fn distance_separator(input: &str) -> nom::IResult<&str, &str> {
-        alt((
-            delimited(space1, tag("to"), space1),
-            delimited(space0, alt((tag("=>"), tag("->"))), space0),
-        ))
+    fn distance_separator(input: &str) -> IResult<&str, &str, NomError<'_>> {
+        context(
+            "`to`",
+            alt((
+                delimited(space1, tag("to"), space1),
+                delimited(space0, alt((tag("=>"), tag("->"))), space0),
+            )),
+        )
         .parse(input)
     }
 
-    pub fn distance_parser(input: &str) -> nom::IResult<&str, (&str, &str, u16)> {
+    pub fn distance_parser(input: &str) -> IResult<&str, (&str, &str, u16), NomError<'_>> {
         let (remainder, (src, dst)) =
             separated_pair(vertex_name, distance_separator, vertex_name).parse(input)?;
-        let (remainder, _) = delimited(space0, tag("="), space0).parse(remainder)?;
-        let (remainder, value) = complete::u16(remainder)?;
+        let (remainder, _) =
+            context("`=`", delimited(space0, tag("="), space0)).parse(remainder)?;
+        let (remainder, value) = context("a distance", complete::u16).parse(remainder)?;
 
         Ok((remainder, (src, dst, value)))
     }
 
     pub fn try_parse_edge(input_line: &str) -> Result<Edge> {
         let (_, (src, dst, value)) =
-            distance_parser(input_line).map_err(|e| InputError::Parsing(format!("{}", e)))?;
+            distance_parser(input_line).map_err(|e| Diagnostic::from_nom(input_line, &e))?;
 
         Ok(Edge::new(src, dst, value))
     }
 }
 
-fn traverse_graph(
-    graph: &AdjacencyList,
-    current: &Vertex,
-    path: &mut Vec<Vertex>,
-    visited: &mut HashMap<Vertex, bool>,
-    paths: &mut Vec<(Vec<Vertex>, u32)>,
-    mut acc: u32,
-) -> bool {
-    debug!("Visiting {}", current.0);
-    if let Some(&current_visited) = visited.get(current) {
-        if current_visited {
-            debug!("{} Already visited", current.0);
-            return false;
-        }
-    }
-    // Pre
-    path.push(current.clone());
-    visited.insert(current.clone(), true);
-
-    let ppath = path.iter().map(|v| v.0.clone()).join(" -> ");
-    debug!("[{}] {}", acc, ppath);
-
-    let all_visited: bool = visited.values().all(|&v| v);
-    if all_visited {
-        paths.push((path.clone(), acc));
-        debug!("Walked all vertexes. Current path was stored");
-        path.pop();
-        visited.insert(current.clone(), false);
-        return true;
-    }
+/// Largest graph [`best_route`] takes, its tables growing as `2^n * n`
+pub const MAX_ROUTE_VERTEXES: usize = 20;
 
-    // Recourse
-    if let Some(current_edges) = graph.get(current) {
-        for (next, edge_value) in current_edges {
-            acc += u32::from(*edge_value);
-            traverse_graph(graph, next, path, visited, paths, acc);
-            acc -= u32::from(*edge_value);
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    Shortest,
+    Longest,
+}
+
+impl Objective {
+    fn is_better(self, distance: u32, than: u32) -> bool {
+        match self {
+            Self::Shortest => distance < than,
+            Self::Longest => distance > than,
         }
     }
+}
 
-    // Post
-    path.pop();
-    visited.insert(current.clone(), false);
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouteKind {
+    /// Visits every vertex once, starting and ending anywhere
+    Open,
+    /// Visits every vertex once and returns to the first one
+    Closed,
+}
 
-    false
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    /// Vertexes in visiting order. Closed routes start at the first vertex of the graph and
+    /// repeat it at the end.
+    pub vertexes: Vec<Vertex>,
+    pub distance: u32,
 }
 
-fn search_full_paths_on_graph(graph: &AdjacencyList) -> Vec<(Vec<Vertex>, u32)> {
-    let mut paths: Vec<(Vec<Vertex>, u32)> = Vec::default();
+impl std::fmt::Display for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.vertexes.iter().map(|v| v.0.as_str()).join(" -> ");
+        write!(f, "[{}] {}", self.distance, path)
+    }
+}
 
-    for start_vertex in graph.vertexes.iter() {
-        // debug!("\n{:?} ----------------------------------", &start_vertex);
+/// Table entry of a subset and last vertex without any path
+const UNREACHED: u32 = u32::MAX;
+/// Predecessor of the first vertex of a path, the vertexes being few enough for a byte
+const NO_VERTEX: u8 = u8::MAX;
+
+/// Shortest or longest route through every vertex of the graph, found with the Held–Karp
+/// dynamic programming over subsets of vertexes. `None` when no route visits them all.
+pub fn best_route(graph: &Cities, objective: Objective, kind: RouteKind) -> Result<Option<Route>> {
+    let n = graph.len();
+    if n == 0 {
+        return Ok(None);
+    }
+    if n > MAX_ROUTE_VERTEXES {
+        return Err(anyhow::anyhow!(
+            "Routes through {n} vertexes are not supported, the limit is {MAX_ROUTE_VERTEXES}"
+        )
+        .into());
+    }
 
-        let mut visited: HashMap<Vertex, bool> =
-            HashMap::from_iter(graph.vertexes.iter().map(|v| (v.clone(), false)));
-        // dbg!(&visited);
+    let distances = graph
+        .vertex_ids()
+        .map(|from| {
+            graph
+                .vertex_ids()
+                .map(|to| graph.edge(from, to).map(|&value| u32::from(value)))
+                .collect_vec()
+        })
+        .collect_vec();
+
+    // `best[mask * n + last]` is the best distance of the paths through the vertexes of `mask`
+    // ending at `last`, and `previous` the vertex before `last` on that path
+    let full = (1usize << n) - 1;
+    let mut best = vec![UNREACHED; (full + 1) * n];
+    let mut previous = vec![NO_VERTEX; (full + 1) * n];
+    match kind {
+        RouteKind::Open => (0..n).for_each(|v| best[(1 << v) * n + v] = 0),
+        // Every tour goes through the first vertex, so it can start there
+        RouteKind::Closed => best[n] = 0,
+    }
 
-        let mut path: Vec<Vertex> = Vec::default();
-        // dbg!(&path);
-        traverse_graph(graph, start_vertex, &mut path, &mut visited, &mut paths, 0);
-        // dbg!(&paths);
+    for mask in 1..=full {
+        cancel::check()?;
+        for last in (0..n).filter(|&v| mask & (1 << v) != 0) {
+            let distance = best[mask * n + last];
+            if distance == UNREACHED {
+                continue;
+            }
+            for next in (0..n).filter(|&v| mask & (1 << v) == 0) {
+                let Some(edge) = distances[last][next] else {
+                    continue;
+                };
+                let index = (mask | 1 << next) * n + next;
+                if best[index] == UNREACHED || objective.is_better(distance + edge, best[index]) {
+                    best[index] = distance + edge;
+                    previous[index] = last as u8;
+                }
+            }
+        }
+    }
+
+    // Distance of the whole route ending at each vertex, back to the start for closed routes
+    let closing = |last: usize| match kind {
+        RouteKind::Open => Some(0),
+        RouteKind::Closed if n == 1 => Some(0),
+        RouteKind::Closed => distances[last][0],
+    };
+    let end = (0..n)
+        .filter_map(|last| {
+            let distance = best[full * n + last];
+            let closing = closing(last)?;
+            (distance != UNREACHED).then(|| (last, distance + closing))
+        })
+        .reduce(|a, b| if objective.is_better(b.1, a.1) { b } else { a });
+    let Some((last, distance)) = end else {
+        return Ok(None);
+    };
+
+    let mut path = vec![last];
+    let mut mask = full;
+    let mut current = last;
+    while previous[mask * n + current] != NO_VERTEX {
+        let before = previous[mask * n + current] as usize;
+        mask &= !(1 << current);
+        current = before;
+        path.push(current);
+    }
+    path.reverse();
+    if kind == RouteKind::Closed && n > 1 {
+        path.push(path[0]);
     }
 
-    paths
+    let route = Route {
+        vertexes: path.into_iter().map(|v| graph.vertex(v).clone()).collect(),
+        distance,
+    };
+    debug!("{}", route);
+    Ok(Some(route))
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::input;
+    use crate::input::{self, error::InputError};
+    use crate::solution::error::SolutionError;
 
     use super::*;
-    use tracing_subscriber::{EnvFilter, fmt, prelude::*};
+    use crate::graph::VertexId;
 
     #[test]
     fn test_distance_parser() {
@@ -296,7 +314,6 @@ mod tests {
         for (input, expected_result) in validations.into_iter() {
             let (_, result) = parsing::distance_parser(input).unwrap();
             assert_eq!(result, expected_result);
-            dbg!(result);
         }
     }
 
@@ -317,55 +334,156 @@ mod tests {
             ),
         ];
 
-        let mut graph = AdjacencyList::default();
         for (input, expected_result) in validations.into_iter() {
             let result = parsing::try_parse_edge(input).unwrap();
             assert_eq!(result, expected_result);
-            graph.try_add_edge(result).unwrap();
         }
     }
 
     #[test]
-    fn test_part_1a() {
-        tracing_subscriber::registry()
-            .with(fmt::layer())
-            .with(EnvFilter::from_default_env())
-            .init();
-
-        let examples = [(
-            vec![
-                "London to Dublin = 464",
-                "London to Belfast = 518",
-                "Dublin to Belfast = 141",
-            ],
-            605,
-        )];
-
-        for (k, example) in examples.iter().enumerate() {
-            debug!("\nExample {k} ========================================================");
-
-            // Build adjacency list
-            let mut graph = AdjacencyList::default();
-            for input_line in example.0.iter() {
-                let edge = parsing::try_parse_edge(input_line).unwrap();
-                graph.try_add_edge(edge).unwrap();
+    fn test_malformed_line() {
+        let input = "London to Dublin = 464\nLondon to Belfast 518\nDublin to Belfast = 141";
+
+        match <Day9 as TypedSolution>::parse(input) {
+            Err(SolutionError::Input(InputError::Parsing { diagnostic, .. })) => {
+                assert_eq!((diagnostic.line, diagnostic.columns), (2, 19..22));
+                assert_eq!(diagnostic.source_line, "London to Belfast 518");
+                assert_eq!(diagnostic.expected.as_deref(), Some("`=`"));
+                assert_eq!(diagnostic.found, "518");
             }
+            r => panic!("Should have been a parsing error, got {r:?}"),
+        }
+    }
+
+    fn parse(input: &str) -> Cities {
+        <Day9 as TypedSolution>::parse(input).unwrap()
+    }
+
+    /// Best route by trying every order of the vertexes
+    fn brute_force(graph: &Cities, objective: Objective, kind: RouteKind) -> Option<u32> {
+        let distance = |a: VertexId, b: VertexId| graph.edge(a, b).map(|&d| u32::from(d));
+        graph
+            .vertex_ids()
+            .permutations(graph.len())
+            .filter_map(|order| {
+                let mut stops = order.clone();
+                if kind == RouteKind::Closed {
+                    stops.push(order[0]);
+                }
+                stops.windows(2).map(|w| distance(w[0], w[1])).sum()
+            })
+            .reduce(|a, b| if objective.is_better(b, a) { b } else { a })
+    }
+
+    #[test]
+    fn test_best_route() {
+        let graph =
+            parse("London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141");
+        let validations = vec![
+            (
+                Objective::Shortest,
+                RouteKind::Open,
+                605,
+                vec!["Belfast", "Dublin", "London"],
+            ),
+            (
+                Objective::Longest,
+                RouteKind::Open,
+                982,
+                vec!["Belfast", "London", "Dublin"],
+            ),
+            (
+                Objective::Shortest,
+                RouteKind::Closed,
+                1123,
+                vec!["London", "Dublin", "Belfast", "London"],
+            ),
+        ];
 
-            let paths = search_full_paths_on_graph(&graph);
+        for (objective, kind, expected_distance, expected_vertexes) in validations.into_iter() {
+            let route = best_route(&graph, objective, kind).unwrap().unwrap();
+            assert_eq!(route.distance, expected_distance);
+            let vertexes = route.vertexes.iter().map(|v| v.0.as_str()).collect_vec();
+            // Routes are found in either direction
+            let reversed = expected_vertexes.iter().rev().copied().collect_vec();
+            assert!(
+                vertexes == expected_vertexes || vertexes == reversed,
+                "{route}"
+            );
+        }
+    }
 
-            debug!("\n**************************************************");
-            for (p, res) in paths.iter() {
-                let ppath = p.iter().map(|v| v.0.clone()).join(" -> ");
-                debug!("[{}] {}", res, ppath);
+    #[test]
+    fn test_route_matches_brute_force() {
+        let lines: Vec<String> = input::parse_file_lines("../inputs/2015/day9.txt").unwrap();
+        let graph = parse(&lines.join("\n"));
+
+        for objective in [Objective::Shortest, Objective::Longest] {
+            for kind in [RouteKind::Open, RouteKind::Closed] {
+                let route = best_route(&graph, objective, kind).unwrap().unwrap();
+                assert_eq!(Some(route.distance), brute_force(&graph, objective, kind));
+
+                // The route goes through every vertex once and adds up to its distance
+                let id = |vertex: &Vertex| graph.id(vertex).unwrap();
+                let stops = route.vertexes.len() - (kind == RouteKind::Closed) as usize;
+                assert_eq!(
+                    route.vertexes.iter().take(stops).unique().count(),
+                    graph.len()
+                );
+                let distance: u32 = route
+                    .vertexes
+                    .windows(2)
+                    .map(|w| u32::from(*graph.edge(id(&w[0]), id(&w[1])).unwrap()))
+                    .sum();
+                assert_eq!(distance, route.distance);
             }
-            debug!("**************************************************");
-            let shortest_path = paths.iter().min_by_key(|&p| p.1).unwrap();
-            let ppath = shortest_path.0.iter().map(|v| v.0.clone()).join(" -> ");
-            debug!("[{}] {}", shortest_path.1, ppath);
-            debug!("**************************************************");
-            assert_eq!(shortest_path.1, example.1);
         }
-        panic!()
+    }
+
+    #[test]
+    fn test_unreachable_route() {
+        let validations = vec![
+            ("A to B = 1\nC to D = 1", RouteKind::Open, None),
+            // Every route through a star ends at two of its leaves, so three leaves are too many
+            ("A to B = 1\nA to C = 2\nA to D = 3", RouteKind::Open, None),
+            ("A to B = 1\nA to C = 2", RouteKind::Open, Some(3)),
+            ("A to B = 1\nA to C = 2", RouteKind::Closed, None),
+            ("A to B = 4", RouteKind::Closed, Some(8)),
+        ];
+
+        for (input, kind, expected_result) in validations.into_iter() {
+            let route = best_route(&parse(input), Objective::Shortest, kind).unwrap();
+            assert_eq!(route.map(|r| r.distance), expected_result, "{input}");
+        }
+
+        let empty = Cities::new();
+        assert_eq!(
+            best_route(&empty, Objective::Shortest, RouteKind::Open).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let input = "London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141";
+        let dot = Day9::new().to_dot(&parse(input)).unwrap().unwrap();
+
+        assert!(dot.starts_with("graph \"day9\" {\n"));
+        assert!(dot.contains("label=\"Shortest route: 605 (blue)\\nLongest route: 982 (red)\";"));
+        assert!(dot.contains("    0 [label=\"London\"];\n"));
+        assert!(dot.contains("    0 -- 1 [label=\"464\", color=\"gray\"];\n"));
+        // London, Dublin and Belfast in either direction
+        let shortest = ["1 -- 2 [color=\"blue\"", "2 -- 1 [color=\"blue\""];
+        assert!(shortest.iter().any(|step| dot.contains(step)));
+        assert_eq!(dot.matches("color=\"red\"").count(), 2);
+    }
+
+    #[test]
+    fn test_too_many_vertexes() {
+        let input = (0..=MAX_ROUTE_VERTEXES)
+            .map(|i| format!("V{} to V{} = 1", i, i + 1))
+            .join("\n");
+        assert!(best_route(&parse(&input), Objective::Shortest, RouteKind::Open).is_err());
     }
 
     #[test]
@@ -378,21 +496,16 @@ mod tests {
         for (input_file_path, expected_result) in test_files_setups.iter() {
             let lines: Vec<String> = input::parse_file_lines(input_file_path).unwrap();
 
-            let mut graph = AdjacencyList::default();
+            let mut graph = Cities::new();
             for line in lines.iter() {
                 let edge = parsing::try_parse_edge(line).unwrap();
-                graph.try_add_edge(edge).unwrap();
-            }
-            let paths = search_full_paths_on_graph(&graph);
-
-            for (p, _res) in paths.iter() {
-                let ppath = p.iter().map(|v| v.0.clone()).join(" -> ");
-                debug!("{}", ppath);
+                graph.add_edge(edge.left, edge.right, edge.value);
             }
-            let shortest_path = paths.iter().min_by_key(|&p| p.1).unwrap();
+            let shortest_path = best_route(&graph, Objective::Shortest, RouteKind::Open)
+                .unwrap()
+                .unwrap();
 
-            dbg!(shortest_path);
-            assert_eq!(shortest_path.1, *expected_result);
+            assert_eq!(shortest_path.distance, *expected_result);
         }
     }
 }
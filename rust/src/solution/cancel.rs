@@ -0,0 +1,142 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use super::error::{Result, SolutionError};
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// Cooperative cancellation of a running part, either requested through [`CancelToken::cancel`]
+/// or once its deadline has passed.
+///
+/// The runner installs the token of every part with [`CancelToken::scope`], and long running
+/// solutions give up early by calling [`check`] from their loops.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token cancelled once `timeout` has passed
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            deadline: Some(Instant::now() + timeout),
+            ..Self::default()
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Runs `f` with this token as the one checked by [`check`] on the current thread
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+}
+
+/// Puts the previous token back once a scope ends, even when it ends by panicking
+struct Restore(Option<CancelToken>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.replace(self.0.take()));
+    }
+}
+
+/// Token of the current thread, to be installed with [`CancelToken::scope`] on the threads that
+/// a part spawns
+pub fn current() -> CancelToken {
+    CURRENT.with(|current| current.borrow().clone().unwrap_or_default())
+}
+
+/// Fails with [`SolutionError::Cancelled`] once the token of the current thread is cancelled.
+/// Outside of [`CancelToken::scope`] it never fails.
+pub fn check() -> Result<()> {
+    let cancelled =
+        CURRENT.with(|current| current.borrow().as_ref().is_some_and(|t| t.is_cancelled()));
+    match cancelled {
+        true => Err(SolutionError::Cancelled),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check().is_ok());
+
+        let token = CancelToken::new();
+        token.scope(|| {
+            assert!(check().is_ok());
+            token.cancel();
+            assert!(matches!(check(), Err(SolutionError::Cancelled)));
+        });
+        assert!(check().is_ok());
+
+        let token = CancelToken::with_timeout(Duration::from_millis(20));
+        token.scope(|| {
+            assert!(check().is_ok());
+            std::thread::sleep(Duration::from_millis(30));
+            assert!(check().is_err());
+        });
+    }
+
+    #[test]
+    fn test_current_token() {
+        let token = CancelToken::new();
+        token.scope(|| {
+            let current = current();
+            let in_thread = || std::thread::scope(|s| s.spawn(|| current.scope(check)).join());
+            assert!(in_thread().unwrap().is_ok());
+            token.cancel();
+            assert!(in_thread().unwrap().is_err());
+        });
+        assert!(!current().is_cancelled());
+    }
+
+    #[test]
+    fn test_scope_restored_on_panic() {
+        let outer = CancelToken::new();
+        outer.cancel();
+        outer.scope(|| {
+            let result = std::panic::catch_unwind(|| CancelToken::new().scope(|| panic!("Boom")));
+            assert!(result.is_err());
+            assert!(check().is_err());
+        });
+        assert!(check().is_ok());
+    }
+
+    #[test]
+    fn test_nested_scopes() {
+        let outer = CancelToken::new();
+        outer.cancel();
+        outer.scope(|| {
+            CancelToken::new().scope(|| assert!(check().is_ok()));
+            assert!(check().is_err());
+        });
+    }
+}
@@ -1,3 +1,5 @@
+use crate::input::diagnostic::Diagnostic;
+
 pub type Result<T> = core::result::Result<T, SolutionError>;
 
 #[derive(thiserror::Error, Debug)]
@@ -9,8 +11,28 @@ pub enum SolutionError {
     NotImplementedDay { year: u16, day: u8 },
     #[error("Day {day} is not a valid Advent of Code")]
     InvalidDay { day: u8 },
+    #[error("Day {day} of {year} has nothing to inspect")]
+    NotInspectable { year: u16, day: u8 },
+    #[error("Cancelled before finishing")]
+    Cancelled,
+    #[error("Parsed input does not belong to this solution")]
+    ParsedInputMismatch,
+    #[error(transparent)]
+    Input(#[from] crate::input::error::InputError),
+    #[error(transparent)]
+    Graph(#[from] crate::graph::error::GraphError),
     #[error(transparent)]
     IOError(#[from] std::io::Error),
     #[error(transparent)]
     Other(#[from] anyhow::Error), // source and Display delegate to anyhow::Error
 }
+
+impl SolutionError {
+    /// Diagnostic of the input that could not be parsed, if that is what failed
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Self::Input(e) => e.diagnostic(),
+            _ => None,
+        }
+    }
+}
@@ -1,36 +1,293 @@
+pub mod answer;
 pub mod aoc2015;
+pub mod cancel;
 pub mod error;
+pub mod registry;
 
+use answer::Answer;
 use error::{Result, SolutionError};
-use std::collections::HashMap;
+use registry::SolutionInfo;
+use serde::{Deserialize, Serialize};
+use std::{any::Any, fmt::Display, str::FromStr};
+
+/// Last day of an Advent of Code
+pub const LAST_DAY: u8 = 25;
+
+/// Solutions of every year, in year order
+const YEARS: &[&[SolutionInfo]] = &[aoc2015::SOLUTIONS];
+
+/// Every registered solution, sorted by year and day
+pub fn registry() -> impl Iterator<Item = &'static SolutionInfo> {
+    YEARS.iter().copied().flatten()
+}
+
+/// Registered solutions of a year, sorted by day
+pub fn get_solutions(year: u16) -> Vec<&'static SolutionInfo> {
+    registry().filter(|info| info.year == year).collect()
+}
+
+pub fn get_solution_info(year: u16, day: u8) -> Result<&'static SolutionInfo> {
+    let solutions = get_solutions(year);
+    match solutions.iter().find(|info| info.day == day) {
+        Some(info) => Ok(info),
+        None if solutions.is_empty() => Err(SolutionError::NotImplementedYear { year }),
+        None if (1..=LAST_DAY).contains(&day) => {
+            Err(SolutionError::NotImplementedDay { year, day })
+        }
+        None => Err(SolutionError::InvalidDay { day }),
+    }
+}
 
 pub fn try_get_day_solution(year: u16, day: u8) -> Result<Box<dyn Solution>> {
-    match year {
-        2015 => aoc2015::try_get_day_solution(day),
-        _ => Err(SolutionError::NotImplementedYear { year }),
+    get_solution_info(year, day).map(|info| (info.build)())
+}
+
+/// Result of solving a part
+pub type PartResult = Result<Answer>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", *self as u8)
     }
 }
 
-#[allow(dead_code)]
-pub fn get_solutions(year: u16, day: Option<u8>) -> HashMap<u8, Result<Box<dyn Solution>>> {
-    match year {
-        2015 => match day {
-            Some(d) => {
-                let mut solutions = HashMap::new();
-                let sol = aoc2015::try_get_day_solution(d);
-                solutions.insert(d, sol);
-                solutions
-            }
-            None => aoc2015::get_solutions(),
-        },
-        _ => panic!("Advent of Code {} is not already implemented", year),
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(part: u8) -> core::result::Result<Self, Self::Error> {
+        match part {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            part => Err(format!("invalid part {part}, expected 1 or 2")),
+        }
     }
 }
 
-type PartResult = Result<Vec<String>>;
+impl FromStr for Part {
+    type Err = String;
 
-pub trait Solution {
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        s.parse::<u8>()
+            .map_err(|_| format!("invalid part {s}, expected 1 or 2"))?
+            .try_into()
+    }
+}
+
+impl Serialize for Part {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        Self::try_from(u8::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Input of a solution as returned by [`Solution::parse`]
+pub type ParsedInput = Box<dyn Any>;
+
+/// Solutions are shared by the worker threads of a run, so any state they keep has to be `Sync`.
+/// Long running parts should call [`cancel::check`] from time to time so that they can be timed out.
+pub trait Solution: Send + Sync {
     fn part1(&self, input: &str) -> PartResult;
 
     fn part2(&self, input: &str) -> PartResult;
+
+    fn solve(&self, part: Part, input: &str) -> PartResult {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+
+    /// Parses the input once so that it can be shared by every part. Solutions that do not
+    /// parse their input on their own keep the raw input.
+    fn parse(&self, input: &str) -> Result<ParsedInput> {
+        Ok(Box::new(input.to_string()))
+    }
+
+    /// Solves `part` from an input returned by [`Solution::parse`]
+    fn solve_parsed(&self, part: Part, parsed: &ParsedInput) -> PartResult {
+        let input = parsed
+            .downcast_ref::<String>()
+            .ok_or(SolutionError::ParsedInputMismatch)?;
+        self.solve(part, input)
+    }
+
+    /// Graphviz DOT rendering of the input, for the days whose input is a graph worth looking at
+    fn to_dot(&self, _input: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// Solution that parses its input into [`TypedSolution::Parsed`] once and solves both parts
+/// from it. Every `TypedSolution` is also a [`Solution`].
+pub trait TypedSolution: Send + Sync {
+    type Parsed: 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+
+    fn part1(&self, parsed: &Self::Parsed) -> PartResult;
+
+    fn part2(&self, parsed: &Self::Parsed) -> PartResult;
+
+    /// See [`Solution::to_dot`]
+    fn to_dot(&self, _parsed: &Self::Parsed) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+impl<T: TypedSolution> Solution for T {
+    fn part1(&self, input: &str) -> PartResult {
+        TypedSolution::part1(self, &T::parse(input)?)
+    }
+
+    fn part2(&self, input: &str) -> PartResult {
+        TypedSolution::part2(self, &T::parse(input)?)
+    }
+
+    fn parse(&self, input: &str) -> Result<ParsedInput> {
+        Ok(Box::new(T::parse(input)?))
+    }
+
+    fn solve_parsed(&self, part: Part, parsed: &ParsedInput) -> PartResult {
+        let parsed = parsed
+            .downcast_ref::<T::Parsed>()
+            .ok_or(SolutionError::ParsedInputMismatch)?;
+        match part {
+            Part::One => TypedSolution::part1(self, parsed),
+            Part::Two => TypedSolution::part2(self, parsed),
+        }
+    }
+
+    fn to_dot(&self, input: &str) -> Result<Option<String>> {
+        TypedSolution::to_dot(self, &T::parse(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lengths {}
+
+    impl TypedSolution for Lengths {
+        type Parsed = Vec<usize>;
+
+        fn parse(input: &str) -> Result<Self::Parsed> {
+            Ok(input.lines().map(|line| line.len()).collect())
+        }
+
+        fn part1(&self, parsed: &Self::Parsed) -> PartResult {
+            Ok(parsed.iter().sum::<usize>().into())
+        }
+
+        fn part2(&self, parsed: &Self::Parsed) -> PartResult {
+            Ok(parsed.iter().max().copied().into())
+        }
+    }
+
+    #[test]
+    fn test_typed_solution_adapter() {
+        let solution: Box<dyn Solution> = Box::new(Lengths {});
+        let input = "a\nbbb\ncc";
+
+        assert_eq!(solution.part1(input).unwrap(), Answer::Integer(6));
+        assert_eq!(
+            solution.solve(Part::Two, input).unwrap(),
+            Answer::Integer(3)
+        );
+
+        assert_eq!(solution.to_dot(input).unwrap(), None);
+
+        let parsed = solution.parse(input).unwrap();
+        assert_eq!(parsed.downcast_ref::<Vec<usize>>(), Some(&vec![1, 3, 2]));
+        assert_eq!(
+            solution.solve_parsed(Part::One, &parsed).unwrap(),
+            Answer::Integer(6)
+        );
+        assert_eq!(
+            solution.solve_parsed(Part::Two, &parsed).unwrap(),
+            Answer::Integer(3)
+        );
+    }
+
+    #[test]
+    fn test_parsed_input_mismatch() {
+        let solution: Box<dyn Solution> = Box::new(Lengths {});
+        let parsed: ParsedInput = Box::new("a\nbbb".to_string());
+
+        assert!(matches!(
+            solution.solve_parsed(Part::One, &parsed),
+            Err(SolutionError::ParsedInputMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_parse_part() {
+        let validations = vec![("1", Some(Part::One)), ("2", Some(Part::Two)), ("3", None)];
+
+        for (input, expected_result) in validations.into_iter() {
+            assert_eq!(Part::from_str(input).ok(), expected_result);
+        }
+        assert!(Part::from_str("one").is_err());
+    }
+
+    #[test]
+    fn test_invalid_days() {
+        let days = [0, 26, 200];
+        for d in days {
+            match try_get_day_solution(2015, d) {
+                Err(SolutionError::InvalidDay { day: _d }) => {
+                    assert_eq!(d, _d)
+                }
+                _ => panic!("Should have been InvalidDay {{ day: {d} }} error"),
+            };
+        }
+    }
+
+    #[test]
+    fn test_registry_lookup() {
+        assert_eq!(
+            get_solution_info(2015, 7).unwrap().title,
+            "Some Assembly Required"
+        );
+        assert!(matches!(
+            try_get_day_solution(2015, 25),
+            Err(SolutionError::NotImplementedDay {
+                year: 2015,
+                day: 25
+            })
+        ));
+        assert!(matches!(
+            try_get_day_solution(2016, 1),
+            Err(SolutionError::NotImplementedYear { year: 2016 })
+        ));
+        assert!(get_solutions(2016).is_empty());
+        assert!(registry().is_sorted_by_key(|info| (info.year, info.day)));
+    }
+
+    #[test]
+    fn test_raw_solution_parse() {
+        let solution = try_get_day_solution(2015, 1).unwrap();
+
+        let parsed = solution.parse("(()(()(").unwrap();
+        assert_eq!(
+            solution.solve_parsed(Part::One, &parsed).unwrap(),
+            Answer::Integer(3)
+        );
+    }
 }
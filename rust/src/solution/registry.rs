@@ -0,0 +1,38 @@
+use super::Solution;
+
+/// Metadata of a registered solution, declared once by [`register_solution`]
+#[derive(Debug, Clone, Copy)]
+pub struct SolutionInfo {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub build: fn() -> Box<dyn Solution>,
+}
+
+/// Declares the year, day and title of a solution, built with its `new` constructor
+macro_rules! register_solution {
+    ($solution:ty, year: $year:literal, day: $day:literal, title: $title:literal) => {
+        impl $solution {
+            pub const INFO: $crate::solution::registry::SolutionInfo =
+                $crate::solution::registry::SolutionInfo {
+                    year: $year,
+                    day: $day,
+                    title: $title,
+                    build: || Box::new(<$solution>::new()),
+                };
+        }
+    };
+}
+
+/// Collects the solutions of the day modules of a year into `SOLUTIONS`. The modules are
+/// declared next to it as plain `mod` items, so that rustfmt formats them.
+macro_rules! year_solutions {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        /// Every solution of the year, in day order
+        pub const SOLUTIONS: &[$crate::solution::registry::SolutionInfo] =
+            &[$($module::$solution::INFO),*];
+    };
+}
+
+pub(crate) use register_solution;
+pub(crate) use year_solutions;
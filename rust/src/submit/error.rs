@@ -0,0 +1,23 @@
+use crate::{client::error::ClientError, solution::answer::Answer};
+
+pub type Result<T> = core::result::Result<T, SubmitError>;
+
+#[derive(thiserror::Error, Debug)]
+#[error("Submit Error")]
+pub enum SubmitError {
+    #[error("Refusing to submit {answer:?}: {reason}")]
+    Refused { answer: String, reason: String },
+    #[error("Answer must be a single non empty line, got {0:?}")]
+    InvalidAnswer(Answer),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error(
+        "No user data directory for the submission history, set {}",
+        super::HISTORY_FILE_ENV
+    )]
+    NoHistoryPath,
+    #[error("Error parsing submission history: {0}")]
+    Parsing(#[from] serde_json::Error),
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+}
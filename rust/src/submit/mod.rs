@@ -0,0 +1,424 @@
+pub mod error;
+
+use error::{Result, SubmitError};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    client::Client,
+    solution::{Part, answer::Answer},
+};
+
+/// Environment variable overriding the path of the submission history
+pub const HISTORY_FILE_ENV: &str = "AOC_HISTORY_FILE";
+const HISTORY_FILE_NAME: &str = "submissions.json";
+
+/// Outcome of a submitted answer, as reported by the website
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong answer without a hint about its magnitude
+    Wrong,
+    /// The answer was not checked, `wait` is the remaining time when the website gives it
+    RateLimited {
+        wait: Option<String>,
+    },
+    /// The part was already solved, so the answer was not checked
+    AlreadySolved,
+    /// Response that could not be recognized, with the text of the page
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Whether the answer was checked and found to be wrong
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "Correct"),
+            Self::TooHigh => write!(f, "Wrong, too high"),
+            Self::TooLow => write!(f, "Wrong, too low"),
+            Self::Wrong => write!(f, "Wrong"),
+            Self::RateLimited { wait: Some(wait) } => {
+                write!(f, "Rate limited, {} left to wait", wait)
+            }
+            Self::RateLimited { wait: None } => write!(f, "Rate limited"),
+            Self::AlreadySolved => write!(f, "Already solved"),
+            Self::Unknown(text) => write!(f, "Unknown response: {}", text),
+        }
+    }
+}
+
+/// Reads the verdict out of the page returned after submitting an answer
+pub fn parse_response(page: &str) -> Verdict {
+    let article_re = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let tag_re = Regex::new(r"<[^>]*>").unwrap();
+    let article = article_re
+        .captures(page)
+        .and_then(|c| c.get(1))
+        .map_or(page, |m| m.as_str());
+    let text = tag_re
+        .replace_all(article, "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("You gave an answer too recently") {
+        let wait_re = Regex::new(r"You have (.+?) left to wait").unwrap();
+        Verdict::RateLimited {
+            wait: wait_re.captures(&text).map(|c| c[1].to_string()),
+        }
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if text.contains("Did you already complete it") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+/// A submitted answer and its verdict, `timestamp` in seconds since the Unix epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub timestamp: u64,
+}
+
+impl Attempt {
+    pub fn new(year: u16, day: u8, part: Part, answer: &str, verdict: Verdict) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// Every answer submitted so far, in submission order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Loads the history at `path`. A missing file means nothing was submitted yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    pub fn attempts(&self, year: u16, day: u8, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| (a.year, a.day, a.part) == (year, day, part))
+    }
+
+    /// Refuses answers that the previous attempts already tell to be wrong: a part that is
+    /// already solved, an answer that was wrong before, or a number outside the known bounds
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<()> {
+        let refuse = |reason: String| {
+            Err(SubmitError::Refused {
+                answer: answer.to_string(),
+                reason,
+            })
+        };
+        let value = Answer::from_line(answer);
+
+        for attempt in self.attempts(year, day, part) {
+            let previous = Answer::from_line(&attempt.answer);
+            match (&attempt.verdict, &value, &previous) {
+                (Verdict::Correct | Verdict::AlreadySolved, _, _) => {
+                    return refuse(format!("part {} is already solved", part));
+                }
+                (verdict, value, previous) if verdict.is_wrong() && value == previous => {
+                    return refuse(format!("it was already submitted: {}", verdict));
+                }
+                (Verdict::TooHigh, Answer::Integer(value), Answer::Integer(high))
+                    if value >= high =>
+                {
+                    return refuse(format!("{} was already too high", high));
+                }
+                (Verdict::TooLow, Answer::Integer(value), Answer::Integer(low)) if value <= low => {
+                    return refuse(format!("{} was already too low", low));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Submits the answer unless the history refuses it, recording the attempt in the history at
+/// `history_path`
+pub fn submit(
+    client: &Client,
+    history_path: &Path,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict> {
+    let mut history = History::load(history_path)?;
+    history.check(year, day, part, answer)?;
+
+    let page = client.submit_answer(year, day, part, answer)?;
+    let verdict = parse_response(&page);
+    history.record(Attempt::new(year, day, part, answer, verdict.clone()));
+    history.save(history_path)?;
+    Ok(verdict)
+}
+
+/// Path of the submission history, taken from [`HISTORY_FILE_ENV`] when it is set. Otherwise it
+/// is kept in the user data directory, as it is personal and does not belong in the repository.
+pub fn get_history_path() -> Result<PathBuf> {
+    if let Some(path) = env::var_os(HISTORY_FILE_ENV) {
+        return Ok(PathBuf::from(path));
+    }
+    let data_dir = match (env::var_os("XDG_DATA_HOME"), env::var_os("HOME")) {
+        (Some(dir), _) => PathBuf::from(dir),
+        (None, Some(home)) => PathBuf::from(home).join(".local").join("share"),
+        (None, None) => return Err(SubmitError::NoHistoryPath),
+    };
+    Ok(data_dir.join("aoc").join(HISTORY_FILE_NAME))
+}
+
+/// Single line answer of a part, the only kind the website accepts
+pub fn single_answer(answer: Answer) -> Result<String> {
+    match &answer {
+        Answer::Integer(value) => Ok(value.to_string()),
+        Answer::Text(line) if !line.trim().is_empty() => Ok(line.trim().to_string()),
+        Answer::Lines(lines) => match lines.as_slice() {
+            [line] if !line.trim().is_empty() => Ok(line.trim().to_string()),
+            _ => Err(SubmitError::InvalidAnswer(answer)),
+        },
+        _ => Err(SubmitError::InvalidAnswer(answer)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{mock_server, test_client};
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let validations = vec![
+            (
+                page("That's the right answer!  You are <em>one gold star</em> closer."),
+                Verdict::Correct,
+            ),
+            (
+                page("That's not the right answer; your answer is too high.  If you're stuck..."),
+                Verdict::TooHigh,
+            ),
+            (
+                page("That's not the right answer; your answer is too low."),
+                Verdict::TooLow,
+            ),
+            (
+                page("That's not the right answer.  If you're stuck, make sure..."),
+                Verdict::Wrong,
+            ),
+            (
+                page(
+                    "You gave an answer too recently; you have to wait after submitting an \
+                      answer before trying again.  You have 38s left to wait.",
+                ),
+                Verdict::RateLimited {
+                    wait: Some("38s".to_string()),
+                },
+            ),
+            (
+                page("You don't seem to be solving the right level.  Did you already complete it?"),
+                Verdict::AlreadySolved,
+            ),
+            (
+                "<p>Maintenance</p>".to_string(),
+                Verdict::Unknown("Maintenance".to_string()),
+            ),
+        ];
+
+        for (page, expected) in validations {
+            assert_eq!(parse_response(&page), expected, "{}", page);
+        }
+    }
+
+    #[test]
+    fn test_history_check() {
+        let mut history = History::default();
+        history.record(Attempt::new(2015, 1, Part::One, "300", Verdict::TooHigh));
+        history.record(Attempt::new(2015, 1, Part::One, "100", Verdict::TooLow));
+        history.record(Attempt::new(2015, 1, Part::One, "200", Verdict::Wrong));
+        history.record(Attempt::new(
+            2015,
+            1,
+            Part::One,
+            "150",
+            Verdict::RateLimited { wait: None },
+        ));
+        history.record(Attempt::new(2015, 2, Part::Two, "42", Verdict::Correct));
+
+        let validations = vec![
+            (2015, 1, Part::One, "200", false),
+            (2015, 1, Part::One, "300", false),
+            (2015, 1, Part::One, "301", false),
+            (2015, 1, Part::One, "100", false),
+            (2015, 1, Part::One, "-5", false),
+            // Compared as numbers, even beyond the range of an i64
+            (2015, 1, Part::One, "+200", false),
+            (2015, 1, Part::One, "99999999999999999999", false),
+            (2015, 1, Part::One, "150", true),
+            (2015, 1, Part::One, "299", true),
+            (2015, 1, Part::One, "abc", true),
+            (2015, 1, Part::Two, "200", true),
+            (2015, 2, Part::Two, "43", false),
+            (2016, 1, Part::One, "200", true),
+        ];
+
+        for (year, day, part, answer, accepted) in validations {
+            let result = history.check(year, day, part, answer);
+            assert_eq!(
+                result.is_ok(),
+                accepted,
+                "{} {} {} {}",
+                year,
+                day,
+                part,
+                answer
+            );
+            if let Err(e) = result {
+                assert!(matches!(e, SubmitError::Refused { .. }));
+            }
+        }
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let path = env::temp_dir().join(format!("aoc-history-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(History::load(&path).unwrap(), History::default());
+
+        let mut history = History::default();
+        history.record(Attempt::new(
+            2015,
+            4,
+            Part::Two,
+            "9962624",
+            Verdict::Correct,
+        ));
+        history.record(Attempt::new(
+            2015,
+            4,
+            Part::One,
+            "1",
+            Verdict::RateLimited {
+                wait: Some("1m 2s".to_string()),
+            },
+        ));
+        history.save(&path).unwrap();
+        assert_eq!(History::load(&path).unwrap(), history);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let path = env::temp_dir().join(format!("aoc-submit-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let too_low = page("That's not the right answer; your answer is too low.");
+        let correct = page("That's the right answer!");
+        let (base_url, requests) = mock_server(vec![(200, too_low.leak()), (200, correct.leak())]);
+        let client = test_client(base_url);
+
+        let validations = vec![
+            ("279", Some(Verdict::TooLow)),
+            ("279", None),
+            ("100", None),
+            ("280", Some(Verdict::Correct)),
+            ("281", None),
+        ];
+        for (answer, expected) in validations {
+            match (submit(&client, &path, 2015, 1, Part::One, answer), expected) {
+                (Ok(verdict), Some(expected)) => assert_eq!(verdict, expected),
+                (Err(SubmitError::Refused { .. }), None) => {}
+                (r, expected) => panic!("{}: expected {:?}, got {:?}", answer, expected, r),
+            }
+        }
+        assert_eq!(requests.try_iter().count(), 2);
+
+        let history = History::load(&path).unwrap();
+        let answers: Vec<_> = history
+            .attempts(2015, 1, Part::One)
+            .map(|a| a.answer.as_str())
+            .collect();
+        assert_eq!(answers, vec!["279", "280"]);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_single_answer() {
+        assert_eq!(single_answer(Answer::Integer(-42)).unwrap(), "-42");
+        assert_eq!(single_answer(Answer::from("abc\n")).unwrap(), "abc");
+        assert_eq!(
+            single_answer(Answer::Lines(vec!["42".into()])).unwrap(),
+            "42"
+        );
+        assert!(single_answer(Answer::NoSolution).is_err());
+        assert!(single_answer(Answer::from(" ")).is_err());
+        assert!(single_answer(Answer::Lines(vec![])).is_err());
+        assert!(single_answer(Answer::Lines(vec!["1".into(), "2".into()])).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::{
+    answers::Answers,
+    examples::Examples,
+    input::{self, DEFAULT_INPUT_NAME},
+    problem::Problem,
+    solution::{Part, answer::Answer},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// There is no known answer to compare with
+    Missing,
+    Error(String),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            Self::Pass => "pass",
+            Self::Fail { .. } => "FAIL",
+            Self::Missing => "missing",
+            Self::Error(_) => "ERROR",
+        };
+        f.pad(status)
+    }
+}
+
+/// Outcome of checking one part of a problem against one of its inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub day: u8,
+    pub input: String,
+    pub part: Part,
+    pub status: Status,
+}
+
+impl Verification {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+/// Solves `parts` of a problem for its default input and for every input with known answers
+pub fn verify_problem(
+    problem: &Problem,
+    answers: &Answers,
+    parts: &[Part],
+    input_dir: &Path,
+) -> Vec<Verification> {
+    let (year, day) = (problem.get_year(), problem.get_day());
+
+    let mut input_names = vec![DEFAULT_INPUT_NAME];
+    input_names.extend(
+        answers
+            .input_names(day)
+            .into_iter()
+            .filter(|&name| name != DEFAULT_INPUT_NAME),
+    );
+
+    let mut verifications = Vec::new();
+    for input_name in input_names {
+        let input_path = input::get_named_input_path(input_dir, year, day, input_name);
+        let input = std::fs::read_to_string(&input_path)
+            .map_err(|e| format!("{}: {}", input_path.display(), e));
+        let expected = |part| answers.get(day, input_name, part);
+        verifications.extend(verify_input(problem, input_name, input, parts, expected));
+    }
+    verifications
+}
+
+/// Solves `parts` of a problem for each of its examples, leaving out the parts an example has no
+/// known answer for
+pub fn verify_examples(
+    problem: &Problem,
+    examples: &Examples,
+    parts: &[Part],
+) -> Vec<Verification> {
+    let day = problem.get_day();
+    let day_examples = match examples.of_day(day) {
+        Ok(day_examples) => day_examples,
+        Err(e) => {
+            let error = Status::Error(e.to_string());
+            return parts
+                .iter()
+                .map(|&part| Verification {
+                    day,
+                    input: "examples".to_string(),
+                    part,
+                    status: error.clone(),
+                })
+                .collect();
+        }
+    };
+
+    let mut verifications = Vec::new();
+    for example in day_examples {
+        let parts: Vec<Part> = parts
+            .iter()
+            .copied()
+            .filter(|&part| examples.expected(&example, part).is_some())
+            .collect();
+        if parts.is_empty() {
+            continue;
+        }
+        let input = example.read().map_err(|e| e.to_string());
+        let expected = |part| examples.expected(&example, part);
+        let input_name = format!("example {}", example.name);
+        verifications.extend(verify_input(problem, &input_name, input, &parts, expected));
+    }
+    verifications
+}
+
+fn verify_input<'a>(
+    problem: &Problem,
+    input_name: &str,
+    input: Result<String, String>,
+    parts: &[Part],
+    expected: impl Fn(Part) -> Option<&'a Answer>,
+) -> Vec<Verification> {
+    let parsed = input.and_then(|input| {
+        problem
+            .get_solution()
+            .parse(&input)
+            .map_err(|e| e.to_string())
+    });
+
+    parts
+        .iter()
+        .map(|&part| {
+            let status = match &parsed {
+                Err(e) => Status::Error(e.clone()),
+                Ok(parsed) => match (
+                    problem.get_solution().solve_parsed(part, parsed),
+                    expected(part),
+                ) {
+                    (Err(e), _) => Status::Error(e.to_string()),
+                    (Ok(_), None) => Status::Missing,
+                    (Ok(actual), Some(expected)) if actual == *expected => Status::Pass,
+                    (Ok(actual), Some(expected)) => Status::Fail {
+                        expected: expected.clone(),
+                        actual,
+                    },
+                },
+            };
+            Verification {
+                day: problem.get_day(),
+                input: input_name.to_string(),
+                part,
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Writes a day/input by part matrix of statuses followed by the details of every failure
+pub fn write_matrix(
+    writer: &mut impl Write,
+    verifications: &[Verification],
+    parts: &[Part],
+) -> io::Result<()> {
+    let input_width = verifications
+        .iter()
+        .map(|v| v.input.len())
+        .chain([5])
+        .max()
+        .unwrap_or_default();
+
+    let mut header = format!("{:>3}  {:<input_width$}", "Day", "Input");
+    for part in parts {
+        header.push_str(&format!("  {:<7}", format!("Part {part}")));
+    }
+    writeln!(writer, "{}", header.trim_end())?;
+
+    let mut rows: Vec<(u8, &str)> = verifications
+        .iter()
+        .map(|v| (v.day, v.input.as_str()))
+        .collect();
+    rows.dedup();
+    for (day, input_name) in rows {
+        let mut row = format!("{:>3}  {:<input_width$}", day, input_name);
+        for part in parts {
+            let status = verifications
+                .iter()
+                .find(|v| (v.day, v.input.as_str(), v.part) == (day, input_name, *part))
+                .map(|v| v.status.to_string())
+                .unwrap_or_default();
+            row.push_str(&format!("  {:<7}", status));
+        }
+        writeln!(writer, "{}", row.trim_end())?;
+    }
+
+    for verification in verifications.iter().filter(|v| v.is_failure()) {
+        let title = format!(
+            "Day {} ({}) Part {}",
+            verification.day, verification.input, verification.part
+        );
+        match &verification.status {
+            // Written as JSON, so that e.g. a number and the same digits as text can be told apart
+            Status::Fail { expected, actual } => writeln!(
+                writer,
+                "{}: expected {}, got {}",
+                title,
+                serde_json::to_string(expected)?,
+                serde_json::to_string(actual)?
+            )?,
+            Status::Error(e) => writeln!(writer, "{}: {}", title, e)?,
+            _ => (),
+        }
+    }
+
+    let count = |f: fn(&Status) -> bool| verifications.iter().filter(|v| f(&v.status)).count();
+    writeln!(
+        writer,
+        "\n{} passed, {} failed, {} missing, {} errors",
+        count(|s| matches!(s, Status::Pass)),
+        count(|s| matches!(s, Status::Fail { .. })),
+        count(|s| matches!(s, Status::Missing)),
+        count(|s| matches!(s, Status::Error(_))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn verifications() -> Vec<Verification> {
+        let answers = Answers::from_str(
+            r#"
+            [day8.default]
+            part1 = 1
+            [day8.jkpr]
+            part1 = 1350
+            [day8.missing]
+            part1 = 1
+            "#,
+        )
+        .unwrap();
+        let problem = Problem::new(2015, 8).unwrap();
+
+        verify_problem(
+            &problem,
+            &answers,
+            &[Part::One, Part::Two],
+            Path::new("../inputs"),
+        )
+    }
+
+    #[test]
+    fn test_verify_problem() {
+        let statuses: Vec<_> = verifications()
+            .into_iter()
+            .map(|v| (v.input, v.part, v.status))
+            .collect();
+
+        assert_eq!(statuses.len(), 6);
+        assert_eq!(
+            statuses[0],
+            (
+                "default".to_string(),
+                Part::One,
+                Status::Fail {
+                    expected: Answer::Integer(1),
+                    actual: Answer::Integer(1342)
+                }
+            )
+        );
+        assert_eq!(statuses[1].2, Status::Missing);
+        assert_eq!(statuses[2], ("jkpr".to_string(), Part::One, Status::Pass));
+        assert_eq!(statuses[3].2, Status::Missing);
+        assert!(matches!(statuses[4].2, Status::Error(_)));
+    }
+
+    #[test]
+    fn test_write_matrix() {
+        let mut buffer = Vec::new();
+        write_matrix(&mut buffer, &verifications(), &[Part::One, Part::Two]).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "Day  Input    Part 1   Part 2");
+        assert_eq!(lines[1], "  8  default  FAIL     missing");
+        assert_eq!(lines[2], "  8  jkpr     pass     missing");
+        assert_eq!(lines[3], "  8  missing  ERROR    ERROR");
+        assert!(lines[4].starts_with("Day 8 (default) Part 1: expected 1, got 1342"));
+        assert_eq!(
+            lines.last(),
+            Some(&"1 passed, 1 failed, 2 missing, 2 errors")
+        );
+    }
+
+    #[test]
+    fn test_verify_examples() {
+        let root = std::env::temp_dir().join(format!("aoc-verify-{}", std::process::id()));
+        let dir = crate::examples::get_examples_path(&root, 2015);
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day3_a.txt"), "^v").unwrap();
+        std::fs::write(dir.join("day3_b.txt"), "^v").unwrap();
+        std::fs::write(dir.join("day3_c.txt"), "^v").unwrap();
+        std::fs::write(
+            dir.join("answers.toml"),
+            "[day3.a]\npart2 = 3\n[day3.b]\npart1 = 3\npart2 = 3",
+        )
+        .unwrap();
+
+        let examples = Examples::load(&root, 2015).unwrap();
+        let problem = Problem::new(2015, 3).unwrap();
+        let statuses: Vec<_> = verify_examples(&problem, &examples, &[Part::One, Part::Two])
+            .into_iter()
+            .map(|v| (v.input, v.part, v.status))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("example a".to_string(), Part::Two, Status::Pass),
+                (
+                    "example b".to_string(),
+                    Part::One,
+                    Status::Fail {
+                        expected: Answer::Integer(3),
+                        actual: Answer::Integer(2)
+                    }
+                ),
+                ("example b".to_string(), Part::Two, Status::Pass),
+            ]
+        );
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_verify_known_answers() {
+        let answers = Answers::load(2015).unwrap();
+
+        // Days that are fast enough to be solved on every test run
+        for day in [1, 2, 3, 5, 8] {
+            let problem = Problem::new(2015, day).unwrap();
+            let verifications = verify_problem(
+                &problem,
+                &answers,
+                &[Part::One, Part::Two],
+                &input::get_input_base_path(),
+            );
+            for verification in verifications {
+                assert_eq!(verification.status, Status::Pass, "{:?}", verification);
+            }
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+    process::{Command, Output},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    examples::Examples,
+    input,
+    problem::selection::PartSelection,
+    solution::{Part, answer::Answer},
+};
+
+/// Manifest directory of the crate that gets rebuilt, resolved at build time
+const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+/// Day to be watched and how its parts are run
+pub struct Watch {
+    pub year: u16,
+    pub day: u8,
+    pub part: PartSelection,
+    pub source_dir: PathBuf,
+    pub input_dir: PathBuf,
+    /// Time between two checks of the watched files
+    pub interval: Duration,
+}
+
+/// Result of a part, as written by the `ndjson` output of a run
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PartRun {
+    pub part: Part,
+    /// `None` both when the part failed and when it has no solution, which is written as `null`
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub elapsed_ns: u64,
+}
+
+/// Outcome of the tests of the day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed(usize),
+    Failed { passed: usize, failed: usize },
+    BuildFailed,
+}
+
+impl Watch {
+    /// Source file of the day, every `day{day}.txt` or `day{day}_{name}.txt` input of it and its
+    /// examples
+    pub fn watched_paths(&self) -> Vec<PathBuf> {
+        let day_path = self
+            .source_dir
+            .join("solution")
+            .join(format!("aoc{}", self.year))
+            .join(format!("day{}.rs", self.day));
+        // The default input is watched even before it exists
+        let default_input = input::get_input_path(&self.input_dir, self.year, self.day);
+
+        let mut inputs: Vec<PathBuf> = input::get_input_names(&self.input_dir, self.year, self.day)
+            .unwrap_or_default()
+            .iter()
+            .filter(|&name| name != input::DEFAULT_INPUT_NAME)
+            .map(|name| input::get_named_input_path(&self.input_dir, self.year, self.day, name))
+            .collect();
+        let examples = Examples::load(&self.input_dir, self.year)
+            .and_then(|examples| examples.of_day(self.day))
+            .unwrap_or_default();
+        inputs.extend(examples.into_iter().map(|example| example.path));
+
+        [day_path, default_input]
+            .into_iter()
+            .chain(inputs)
+            .collect()
+    }
+
+    /// Re-runs the day every time one of the watched files changes. Only returns on errors.
+    pub fn run(&self) -> io::Result<()> {
+        let mut previous: BTreeMap<Part, PartRun> = BTreeMap::new();
+        let mut times = modification_times(&self.watched_paths());
+        let mut changed = Vec::new();
+
+        for run in 1.. {
+            let mut stdout = io::stdout().lock();
+            let cause = match changed.as_slice() {
+                [] => String::new(),
+                paths => format!(" after changes to {}", display_names(paths)),
+            };
+            writeln!(
+                stdout,
+                "---- {} Day {}, run #{}{} ----",
+                self.year, self.day, run, cause
+            )?;
+            stdout.flush()?;
+
+            let tests = self.run_tests()?;
+            match self.run_parts()? {
+                Ok(parts) => {
+                    for current in parts {
+                        writeln!(
+                            stdout,
+                            "{}",
+                            format_part(&current, previous.get(&current.part))
+                        )?;
+                        previous.insert(current.part, current);
+                    }
+                }
+                Err(message) => writeln!(stdout, "{}", message)?,
+            }
+            writeln!(stdout, "{}\n", marker(tests, stdout.is_terminal()))?;
+            drop(stdout);
+
+            loop {
+                thread::sleep(self.interval);
+                let current_times = modification_times(&self.watched_paths());
+                changed = changed_paths(&times, &current_times);
+                times = current_times;
+                if !changed.is_empty() {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_tests(&self) -> io::Result<TestOutcome> {
+        let filter = format!("aoc{}::day{}::", self.year, self.day);
+        let output = cargo(&["test", "--quiet", "--", &filter])?;
+
+        match (
+            parse_test_summary(&String::from_utf8_lossy(&output.stdout)),
+            output.status.success(),
+        ) {
+            (Some((passed, 0)), true) => Ok(TestOutcome::Passed(passed)),
+            (Some((passed, failed)), _) => Ok(TestOutcome::Failed { passed, failed }),
+            (None, _) => {
+                io::stderr().write_all(&output.stderr)?;
+                Ok(TestOutcome::BuildFailed)
+            }
+        }
+    }
+
+    /// Runs the selected parts, returning the error output of the run when it fails
+    fn run_parts(&self) -> io::Result<Result<Vec<PartRun>, String>> {
+        let (year, day) = (self.year.to_string(), self.day.to_string());
+        let part = self.part.to_possible_value().unwrap();
+        let input_dir = self.input_dir.display().to_string();
+        let output = cargo(&[
+            "run",
+            "--quiet",
+            "--",
+            &year,
+            &day,
+            "--part",
+            part.get_name(),
+            "--format",
+            "ndjson",
+            "--input-dir",
+            &input_dir,
+        ])?;
+
+        let runs: Result<Vec<PartRun>, _> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(serde_json::from_str)
+            .collect();
+        match (runs, output.status.success()) {
+            (Ok(runs), true) => Ok(Ok(runs)),
+            _ => Ok(Err(String::from_utf8_lossy(&output.stderr)
+                .trim()
+                .to_string())),
+        }
+    }
+}
+
+/// Runs cargo on the crate, with the profile of the running binary
+fn cargo(args: &[&str]) -> io::Result<Output> {
+    let (command, rest) = args.split_first().unwrap();
+    let mut cargo = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    cargo.current_dir(MANIFEST_DIR).arg(command);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    cargo.args(rest).output()
+}
+
+fn modification_times(paths: &[PathBuf]) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// Paths that were modified, created or removed between two checks
+fn changed_paths(
+    before: &BTreeMap<PathBuf, Option<SystemTime>>,
+    after: &BTreeMap<PathBuf, Option<SystemTime>>,
+) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    changed.sort();
+    changed
+}
+
+fn display_names(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| match path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => path.display().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Adds up the `test result` lines of the output of `cargo test`
+fn parse_test_summary(output: &str) -> Option<(usize, usize)> {
+    let re = regex::Regex::new(r"test result: \w+\. (\d+) passed; (\d+) failed").unwrap();
+    re.captures_iter(output)
+        .map(|c| (c[1].parse().unwrap_or(0), c[2].parse().unwrap_or(0)))
+        .reduce(|(p1, f1), (p2, f2)| (p1 + p2, f1 + f2))
+}
+
+/// Answer and time of a part next to the ones of the previous run
+fn format_part(current: &PartRun, previous: Option<&PartRun>) -> String {
+    let describe = |run: &PartRun| match &run.error {
+        Some(error) => format!("Error: {}", error),
+        None => format!(
+            "{} ({:.2?})",
+            run.answer
+                .as_ref()
+                .unwrap_or(&Answer::NoSolution)
+                .lines()
+                .join(" "),
+            Duration::from_nanos(run.elapsed_ns)
+        ),
+    };
+
+    let line = format!("Part {}: {}", current.part, describe(current));
+    match previous {
+        Some(previous) if previous.answer == current.answer && previous.error.is_none() => {
+            format!(
+                "{}, previously {:.2?}",
+                line,
+                Duration::from_nanos(previous.elapsed_ns)
+            )
+        }
+        Some(previous) => format!("{}, previously {}", line, describe(previous)),
+        None => line,
+    }
+}
+
+/// Green or red line telling whether the tests of the day pass
+fn marker(tests: TestOutcome, colored: bool) -> String {
+    let (ok, text) = match tests {
+        TestOutcome::Passed(passed) => (true, format!("✔ {} tests passed", passed)),
+        TestOutcome::Failed { passed, failed } => (
+            false,
+            format!("✘ {} of {} tests failed", failed, passed + failed),
+        ),
+        TestOutcome::BuildFailed => (false, "✘ build failed".to_string()),
+    };
+    match (colored, ok) {
+        (false, _) => text,
+        (true, true) => format!("\x1b[32m{}\x1b[0m", text),
+        (true, false) => format!("\x1b[31m{}\x1b[0m", text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part_run(part: Part, answer: i128, elapsed_ns: u64) -> PartRun {
+        PartRun {
+            part,
+            answer: Some(Answer::Integer(answer)),
+            error: None,
+            elapsed_ns,
+        }
+    }
+
+    #[test]
+    fn test_watched_paths() {
+        let root = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("inputs/2015")).unwrap();
+        fs::create_dir_all(root.join("inputs/2015/examples")).unwrap();
+        fs::write(root.join("inputs/2015/examples/day7_1.txt"), "").unwrap();
+        for name in [
+            "day7.txt",
+            "day7_example.txt",
+            "day70_x.txt",
+            "day7_a.bak",
+            "day8.txt",
+        ] {
+            fs::write(root.join("inputs/2015").join(name), "").unwrap();
+        }
+        let watch = Watch {
+            year: 2015,
+            day: 7,
+            part: PartSelection::Both,
+            source_dir: root.join("src"),
+            input_dir: root.join("inputs"),
+            interval: Duration::from_millis(10),
+        };
+
+        let paths = watch.watched_paths();
+        assert_eq!(
+            paths,
+            vec![
+                root.join("src/solution/aoc2015/day7.rs"),
+                root.join("inputs/2015/day7.txt"),
+                root.join("inputs/2015/day7_example.txt"),
+                root.join("inputs/2015/examples/day7_1.txt"),
+            ]
+        );
+
+        let before = modification_times(&paths);
+        assert_eq!(before[&paths[0]], None);
+        assert!(before[&paths[2]].is_some());
+        assert!(changed_paths(&before, &modification_times(&paths)).is_empty());
+
+        fs::create_dir_all(root.join("src/solution/aoc2015")).unwrap();
+        fs::write(&paths[0], "").unwrap();
+        fs::remove_file(&paths[2]).unwrap();
+        let after = modification_times(&watch.watched_paths());
+        assert_eq!(
+            changed_paths(&before, &after),
+            vec![paths[2].clone(), paths[0].clone()]
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_parse_test_summary() {
+        let output = "\nrunning 3 tests\n...\ntest result: ok. 3 passed; 0 failed; 0 ignored; \
+            0 measured; 102 filtered out; finished in 0.01s\n\n\
+            test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        assert_eq!(parse_test_summary(output), Some((4, 2)));
+        assert_eq!(parse_test_summary("error[E0308]: mismatched types"), None);
+    }
+
+    #[test]
+    fn test_format_part() {
+        let current = part_run(Part::One, 46065, 4_460_000);
+        assert_eq!(format_part(&current, None), "Part 1: 46065 (4.46ms)");
+
+        let previous = part_run(Part::One, 46065, 5_000_000);
+        assert_eq!(
+            format_part(&current, Some(&previous)),
+            "Part 1: 46065 (4.46ms), previously 5.00ms"
+        );
+
+        let previous = part_run(Part::One, 123, 1_000);
+        assert_eq!(
+            format_part(&current, Some(&previous)),
+            "Part 1: 46065 (4.46ms), previously 123 (1.00µs)"
+        );
+
+        let failed = PartRun {
+            answer: None,
+            error: Some("Cancelled before finishing".into()),
+            ..current.clone()
+        };
+        assert_eq!(
+            format_part(&current, Some(&failed)),
+            "Part 1: 46065 (4.46ms), previously Error: Cancelled before finishing"
+        );
+    }
+
+    #[test]
+    fn test_marker() {
+        assert_eq!(marker(TestOutcome::Passed(3), false), "✔ 3 tests passed");
+        assert_eq!(
+            marker(
+                TestOutcome::Failed {
+                    passed: 3,
+                    failed: 1
+                },
+                false
+            ),
+            "✘ 1 of 4 tests failed"
+        );
+        assert_eq!(
+            marker(TestOutcome::BuildFailed, true),
+            "\x1b[31m✘ build failed\x1b[0m"
+        );
+        assert_eq!(
+            marker(TestOutcome::Passed(1), true),
+            "\x1b[32m✔ 1 tests passed\x1b[0m"
+        );
+    }
+}